@@ -0,0 +1,19 @@
+//! Parsing arbitrary input should only ever fail with an error, never panic. \
+//! Run with `cargo +nightly fuzz run parse` from `crates/mangohudlib`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mangohudlib::config;
+
+fuzz_target!(|content: &str| {
+    for config in [config::parse_str(content), config::parse_env(content)]
+        .into_iter()
+        .flatten()
+    {
+        // Also exercise everything that works off parsed values
+        let _ = config.to_config_string();
+        let _ = config.validate();
+        let _ = config.summary();
+    }
+});
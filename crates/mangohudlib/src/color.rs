@@ -1,3 +1,4 @@
+use eyre::Result;
 use rgb::RGB8;
 
 pub const WHITE: RGB8 = RGB8::new(0xff, 0xff, 0xff);
@@ -14,3 +15,91 @@ pub const SOFT_RED: RGB8 = RGB8::new(0xeb, 0x5b, 0x5b);
 pub const LIGHT_RED: RGB8 = RGB8::new(0xff, 0x90, 0x78);
 pub const DARK_RED: RGB8 = RGB8::new(0xb2, 0x22, 0x22);
 pub const VIVID_YELLOW: RGB8 = RGB8::new(0xfd, 0xfd, 0x09);
+
+/// Named colors, in the order [`name_for_color`] prefers them.
+const NAMED_COLORS: &[(&str, RGB8)] = &[
+    ("white", WHITE),
+    ("almost_black", ALMOST_BLACK),
+    ("black", BLACK),
+    ("blue", BLUE),
+    ("light_magenta", LIGHT_MAGENTA),
+    ("light_pink", LIGHT_PINK),
+    ("light_violet", LIGHT_VIOLET),
+    ("green", GREEN),
+    ("lime_green", LIME_GREEN),
+    ("dark_lime_green", DARK_LIME_GREEN),
+    ("soft_red", SOFT_RED),
+    ("light_red", LIGHT_RED),
+    ("dark_red", DARK_RED),
+    ("vivid_yellow", VIVID_YELLOW),
+];
+
+/// Looks up one of the constants in this module by name, case-insensitively, e.g. `green`.
+pub fn color_from_name(name: &str) -> Option<RGB8> {
+    NAMED_COLORS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|&(_, color)| color)
+}
+
+/// The name of the constant matching `color` exactly, if any.
+pub fn name_for_color(color: RGB8) -> Option<&'static str> {
+    NAMED_COLORS
+        .iter()
+        .find(|&&(_, c)| c == color)
+        .map(|&(name, _)| name)
+}
+
+/// Parses a hex color with or without a leading `#`, in either case, e.g. `ffffff` or `#FFF`. \
+/// 3 digit colors are shorthand for doubling each digit, `fff` is `ffffff`.
+pub fn color_from_hex(value: &str) -> Result<RGB8> {
+    let digits = value.strip_prefix('#').unwrap_or(value);
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        eyre::bail!("Expected a hex color, got {value:?} containing {c:?}");
+    }
+
+    let digits = match digits.len() {
+        3 => digits.chars().flat_map(|c| [c, c]).collect(),
+        6 => digits.to_owned(),
+        n => eyre::bail!("Expected a hex color of 3 or 6 digits, got {value:?} with {n}"),
+    };
+    let [r, g, b] = hex::decode(digits)?
+        .try_into()
+        .expect("6 hex digits decode to 3 bytes");
+    Ok(RGB8::new(r, g, b))
+}
+
+/// Formats a color the way MangoHud's configs write them, as 6 lowercase hex digits without
+/// a `#`, e.g. `ffffff`.
+pub fn color_to_hex(color: RGB8) -> String {
+    hex::encode([color.r, color.g, color.b])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors() {
+        for value in ["ffffff", "#ffffff", "FFFFFF", "fff", "#FfF"] {
+            assert_eq!(color_from_hex(value).unwrap(), WHITE, "{value}");
+        }
+        assert_eq!(
+            color_from_hex("1a2b3c").unwrap(),
+            RGB8::new(0x1a, 0x2b, 0x3c)
+        );
+        assert_eq!(color_to_hex(RGB8::new(0xAB, 0x00, 0x0F)), "ab000f");
+
+        assert!(color_from_hex("ffff").is_err());
+        assert!(color_from_hex("").is_err());
+        let error = color_from_hex("ffzzff").unwrap_err().to_string();
+        assert!(error.contains("'z'"), "{error}");
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(color_from_name("White"), Some(WHITE));
+        assert_eq!(name_for_color(WHITE), Some("white"));
+        assert_eq!(color_from_name("no_such_color"), None);
+    }
+}
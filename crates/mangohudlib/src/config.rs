@@ -2,17 +2,59 @@
 //! https://github.com/flightlessmango/MangoHud#environment-variables-mangohud_config-and-mangohud_configfile
 
 use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
     path::{Path, PathBuf},
     str::FromStr,
     time::Duration,
 };
 
-use eyre::Result;
+use eyre::{Result, WrapErr};
 use rgb::RGB8;
-use strum::EnumString;
+use strum::{Display, EnumIter, EnumMessage, EnumString, IntoEnumIterator};
 
 use crate::color::*;
 
+mod builder;
+mod constraint;
+mod diff;
+mod discover;
+mod extra;
+mod hash;
+mod import;
+mod launch;
+mod layout;
+mod lint;
+mod merge;
+mod normalize;
+mod options;
+mod preset;
+mod profile;
+mod resolve;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod serialize;
+mod sparse;
+mod validate;
+mod warning;
+
+pub use builder::MangoHudConfigBuilder;
+pub use constraint::{constraints_for, Constraint};
+pub use diff::*;
+pub use discover::*;
+pub use extra::*;
+pub use import::*;
+pub use launch::LaunchEnv;
+pub use layout::Metric;
+pub use lint::*;
+pub use options::{doc_for, OptionMeta, OPTIONS};
+pub use profile::*;
+pub use resolve::resolve;
+pub use serialize::SerializeOptions;
+pub use sparse::*;
+pub use validate::*;
+pub use warning::*;
+
 // Defaults:
 // https://github.com/flightlessmango/MangoHud/blob/6306fed7f749837f2a780c883743af3a116a5465/src/overlay_params.cpp#L587
 
@@ -22,8 +64,9 @@ use crate::color::*;
 // - NOTE:Ordered only if `legacy_layout` is set to false.
 
 /// https://github.com/flightlessmango/MangoHud#environment-variables-mangohud_config-and-mangohud_configfile
-#[derive(Debug, Default)]
-#[allow(dead_code)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default = "MangoHudConfig::new"))]
 pub struct MangoHudConfig {
     // Performance
     fps_limit: Vec<u16>,
@@ -54,8 +97,9 @@ pub struct MangoHudConfig {
     gpu_mem_clock: bool,
     gpu_power: bool,
     gpu_text: String,
-    gpu_load_change: bool,     // Switch between colors depending on load value
-    gpu_load_value: [u8; 2],   // Medium and High load values
+    gpu_load_change: bool,   // Switch between colors depending on load value
+    gpu_load_value: [u8; 2], // Medium and High load values
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_colors"))]
     gpu_load_color: [RGB8; 3], // Color for before Medium, between Medium and High and after High load
 
     // CPU Info
@@ -66,6 +110,7 @@ pub struct MangoHudConfig {
     cpu_mhz: bool,
     cpu_load_change: bool,
     cpu_load_value: [u8; 2],
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_colors"))]
     cpu_load_color: [RGB8; 3],
     core_load: bool,
     core_load_change: bool,
@@ -92,9 +137,11 @@ pub struct MangoHudConfig {
 
     // FPS Info
     fps: bool,
-    fps_sampling_period: Duration, // Serialize to nanoseconds
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::millis"))]
+    fps_sampling_period: Duration, // Serialize to milliseconds
     fps_color_change: bool,
     fps_value: [u8; 2],
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_colors"))]
     fps_color: [RGB8; 3],
     frametime: bool,
     frame_timing: bool,
@@ -117,7 +164,7 @@ pub struct MangoHudConfig {
 
     // Media Info
     media_player: bool,
-    media_player_name: String, // FIXME use an enum?
+    media_player_name: Option<MediaPlayer>, // `None` for whichever player is playing
     media_player_format: String,
 
     // Hud Font
@@ -128,7 +175,7 @@ pub struct MangoHudConfig {
     no_small_font: bool,
     font_file: PathBuf,
     font_file_text: PathBuf,
-    font_glyph_ranges: Vec<String>,
+    font_glyph_ranges: Vec<GlyphRange>,
     text_outline: bool,
     text_outline_thickness: f32,
 
@@ -155,18 +202,31 @@ pub struct MangoHudConfig {
     fcat_screen_edge: FcatOverlayEdge,
 
     // Color
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     text_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     gpu_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     cpu_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     vram_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     ram_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     engine_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     io_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     frametime_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     background_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     media_player_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     wine_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     battery_color: RGB8,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::hex_color"))]
     text_outline_color: RGB8,
 
     pci_dev: String,
@@ -179,86 +239,404 @@ pub struct MangoHudConfig {
     // gl_dont_flip: ?
 
     // Keybinds
-    toggle_hud: Vec<String>, // FIXME write your own struct for distiction bw left and right shift, etc
-    toggle_hud_position: Vec<String>,
-    toggle_fps_limit: Vec<String>,
-    toggle_logging: Vec<String>,
-    reload_cfg: Vec<String>,
-    upload_log: Vec<String>,
+    toggle_hud: Keybind,
+    toggle_hud_position: Keybind,
+    toggle_fps_limit: Keybind,
+    toggle_logging: Keybind,
+    reload_cfg: Keybind,
+    upload_log: Keybind,
 
     // Logging
     autostart_log: bool,
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::millis"))]
     log_duration: Duration, // Serialize to seconds
+    #[cfg_attr(feature = "serde", serde(with = "serde_impls::millis"))]
     log_interval: Duration, // Serialize to miliseconds
-    output_folder: PathBuf, // FIXME add default
+    output_folder: PathBuf, // Empty for `default_output_folder()`
     permit_upload: bool,
-    benchmark_percentiles: String,
+    benchmark_percentiles: Vec<String>,
+
+    /// Options read from a config that aren't known to this crate, e.g. ones added in newer
+    /// MangoHud versions, kept as is so they aren't lost when writing the config back.
+    extra: BTreeMap<String, ExtraValue>,
+    /// Comment lines at the start of the config file, e.g. `# My config`, written back above
+    /// the options so annotated configs keep their header.
+    header: Vec<String>,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, EnumString)]
-#[strum(ascii_case_insensitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumString, Display, EnumIter, EnumMessage)]
+#[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum FpsLimitMethod {
+    // MangoHud's `fps_limit_method` enum values are accepted as well
+    #[strum(to_string = "early", serialize = "0", message = "Early")]
     Early,
     #[default]
+    #[strum(to_string = "late", serialize = "1", message = "Late")]
     Late,
 }
 
-#[derive(Debug, Default, Clone, Copy, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumString, Display, EnumIter, EnumMessage)]
+#[strum(ascii_case_insensitive)]
 pub enum VSync {
-    #[strum(serialize = "0")]
+    // The names are accepted as well, but MangoHud only understands the numbers
+    #[strum(to_string = "0", serialize = "adaptive", message = "Adaptive")]
     Adaptive = 0,
     #[default]
-    #[strum(serialize = "1")]
+    #[strum(to_string = "1", serialize = "off", message = "Off")]
     Off = 1,
-    #[strum(serialize = "2")]
+    #[strum(to_string = "2", serialize = "mailbox", message = "Mailbox")]
     Mailbox = 2,
-    #[strum(serialize = "3")]
+    #[strum(to_string = "3", serialize = "on", message = "On")]
     On = 3,
 }
 
-#[derive(Debug, Default, Clone, Copy, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumString, Display, EnumIter, EnumMessage)]
 pub enum HudPreset {
     #[default]
-    #[strum(serialize = "-1")]
+    #[strum(serialize = "-1", message = "Default")]
     Default = -1,
-    #[strum(serialize = "0")]
+    #[strum(serialize = "0", message = "Off")]
     Off = 0,
-    #[strum(serialize = "1")]
+    #[strum(serialize = "1", message = "FPS Only")]
     FpsOnly = 1,
-    #[strum(serialize = "2")]
+    #[strum(serialize = "2", message = "Horizontal")]
     Horizontal = 2,
-    #[strum(serialize = "3")]
+    #[strum(serialize = "3", message = "Extended")]
     Extended = 3,
-    #[strum(serialize = "4")]
+    #[strum(serialize = "4", message = "Detailed")]
     Detailed = 4,
 }
 
-#[derive(Debug, Default, Clone, Copy, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumString, Display, EnumIter, EnumMessage)]
 #[strum(serialize_all = "kebab_case")]
 pub enum HudPosition {
     #[default]
+    #[strum(message = "Top Left")]
     TopLeft,
+    #[strum(message = "Top Center")]
     TopCenter,
+    #[strum(message = "Top Right")]
     TopRight,
+    #[strum(message = "Middle Left")]
     MiddleLeft,
+    #[strum(message = "Middle Right")]
     MiddleRight,
+    #[strum(message = "Bottom Left")]
     BottomLeft,
+    #[strum(message = "Bottom Right")]
     BottomRight,
 }
 
-#[derive(Debug, Default, Clone, Copy, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumString, Display, EnumIter, EnumMessage)]
 pub enum FcatOverlayEdge {
     #[default]
-    #[strum(serialize = "0")]
+    #[strum(serialize = "0", message = "Left")]
     Left = 0,
-    #[strum(serialize = "1")]
+    #[strum(serialize = "1", message = "Bottom")]
     Bottom = 1,
-    #[strum(serialize = "2")]
+    #[strum(serialize = "2", message = "Right")]
     Right = 2,
-    #[strum(serialize = "3")]
+    #[strum(serialize = "3", message = "Top")]
     Top = 3,
 }
 
+/// Adds a `label()` returning the variant's human readable name, for UIs.
+macro_rules! impl_label {
+    ($($ty:ty),*) => {$(
+        impl $ty {
+            pub fn label(&self) -> &'static str {
+                self.get_message().expect("every variant should have a message")
+            }
+        }
+    )*};
+}
+
+impl_label!(
+    FpsLimitMethod,
+    VSync,
+    HudPreset,
+    HudPosition,
+    FcatOverlayEdge
+);
+
+/// A modifier key, by the X11 keysym name MangoHud reads it as. Left and right are told apart,
+/// like MangoHud does. \
+/// Names are read ignoring case, and `Ctrl_L` and `Ctrl_R` as `Control_L` and `Control_R`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display, EnumIter,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Modifier {
+    // Declared in the order keybinds list them in
+    #[strum(to_string = "Control_L", serialize = "Ctrl_L")]
+    ControlL,
+    #[strum(to_string = "Control_R", serialize = "Ctrl_R")]
+    ControlR,
+    #[strum(serialize = "Alt_L")]
+    AltL,
+    #[strum(serialize = "Alt_R")]
+    AltR,
+    #[strum(serialize = "Shift_L")]
+    ShiftL,
+    #[strum(serialize = "Shift_R")]
+    ShiftR,
+    #[strum(serialize = "Super_L")]
+    SuperL,
+    #[strum(serialize = "Super_R")]
+    SuperR,
+}
+
+/// Keys pressed together, written joined with `+`, e.g. `Shift_R+F12`. \
+/// Any number of [`Modifier`]s and at most one other key, kept in a canonical order so keybinds
+/// pressing the same keys compare equal and are written the same way.
+/// An unbound keybind is written as an empty value.
+///
+/// Each keybind option, like `toggle_hud`, is a single `Keybind`,
+/// since MangoHud only reads one combination per option.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Keybind {
+    modifiers: Vec<Modifier>,
+    key: Option<String>,
+}
+
+impl Keybind {
+    pub fn new(modifiers: impl IntoIterator<Item = Modifier>, key: Option<String>) -> Self {
+        let mut modifiers: Vec<_> = modifiers.into_iter().collect();
+        modifiers.sort();
+        modifiers.dedup();
+        Self { modifiers, key }
+    }
+
+    /// The modifiers, in the order they're written in.
+    pub fn modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    /// The key pressed along with the modifiers, e.g. `F12`, as its X11 keysym name.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modifiers.is_empty() && self.key.is_none()
+    }
+}
+
+impl FromStr for Keybind {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut modifiers = Vec::new();
+        let mut key: Option<&str> = None;
+        for part in s.split('+').map(str::trim) {
+            if part.is_empty() {
+                eyre::bail!("Keybind {s:?} has an empty key");
+            }
+            if let Ok(modifier) = part.parse::<Modifier>() {
+                modifiers.push(modifier);
+                continue;
+            }
+            if let Some(name) = ["Control", "Ctrl", "Alt", "Shift", "Super"]
+                .into_iter()
+                .find(|name| name.eq_ignore_ascii_case(part))
+            {
+                let name = if name == "Ctrl" { "Control" } else { name };
+                eyre::bail!(
+                    "Expected a modifier with its side like `{name}_L` or `{name}_R`, got {part:?}"
+                );
+            }
+            if let Some(other) = key.replace(part) {
+                eyre::bail!(
+                    "Keybind {s:?} has more than one key besides modifiers: {other:?} and {part:?}"
+                );
+            }
+        }
+
+        Ok(Self::new(modifiers, key.map(Into::into)))
+    }
+}
+
+impl fmt::Display for Keybind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = self.modifiers.iter().map(Modifier::to_string);
+        let keys: Vec<_> = modifiers.chain(self.key.clone()).collect();
+        f.write_str(&keys.join("+"))
+    }
+}
+
+/// An MPRIS media player, by the name it registers on D-Bus as, e.g. `spotify` for
+/// `org.mpris.MediaPlayer2.spotify`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MediaPlayer {
+    Spotify,
+    Vlc,
+    Mpv,
+    Rhythmbox,
+    Clementine,
+    Strawberry,
+    Audacious,
+    Elisa,
+    Lollypop,
+    Firefox,
+    Chromium,
+    /// Any other player, by its D-Bus name.
+    Other(String),
+}
+
+impl MediaPlayer {
+    const NAMED: [(&'static str, MediaPlayer); 11] = [
+        ("spotify", Self::Spotify),
+        ("vlc", Self::Vlc),
+        ("mpv", Self::Mpv),
+        ("rhythmbox", Self::Rhythmbox),
+        ("clementine", Self::Clementine),
+        ("strawberry", Self::Strawberry),
+        ("audacious", Self::Audacious),
+        ("elisa", Self::Elisa),
+        ("lollypop", Self::Lollypop),
+        ("firefox", Self::Firefox),
+        ("chromium", Self::Chromium),
+    ];
+
+    /// The well-known players, e.g. for populating a dropdown next to a free-form input.
+    pub fn known() -> impl Iterator<Item = MediaPlayer> {
+        Self::NAMED.into_iter().map(|(_, player)| player)
+    }
+}
+
+/// D-Bus names are case-sensitive, so only exact matches are read as a well-known player.
+impl FromStr for MediaPlayer {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            eyre::bail!("Expected the name of a media player");
+        }
+
+        Ok(Self::NAMED
+            .into_iter()
+            .find(|&(name, _)| name == s)
+            .map_or_else(|| Self::Other(s.into()), |(_, player)| player))
+    }
+}
+
+impl fmt::Display for MediaPlayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Other(name) => f.write_str(name),
+            named => {
+                let (name, _) = Self::NAMED
+                    .iter()
+                    .find(|(_, player)| player == named)
+                    .expect("every named player should be listed");
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+/// A range of characters to load from the font, either one of MangoHud's named ranges like `korean`
+/// or an explicit range of codepoints like `0x4e00-0x9fff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphRange {
+    Korean,
+    Chinese,
+    ChineseSimplified,
+    Japanese,
+    Cyrillic,
+    Thai,
+    Vietnamese,
+    LatinExtA,
+    LatinExtB,
+    /// Inclusive range of codepoints.
+    Custom(u32, u32),
+}
+
+impl GlyphRange {
+    const NAMED: [(&'static str, GlyphRange); 9] = [
+        ("korean", Self::Korean),
+        ("chinese", Self::Chinese),
+        ("chinese_simplified", Self::ChineseSimplified),
+        ("japanese", Self::Japanese),
+        ("cyrillic", Self::Cyrillic),
+        ("thai", Self::Thai),
+        ("vietnamese", Self::Vietnamese),
+        ("latin_ext_a", Self::LatinExtA),
+        ("latin_ext_b", Self::LatinExtB),
+    ];
+}
+
+impl FromStr for GlyphRange {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(&(_, range)) = Self::NAMED
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        {
+            return Ok(range);
+        }
+
+        let parse_codepoint = |s: &str| {
+            let s = s.trim();
+            let hex = s
+                .strip_prefix("0x")
+                .or_else(|| s.strip_prefix("0X"))
+                .unwrap_or(s);
+            u32::from_str_radix(hex, 16)
+                .ok()
+                .filter(|&c| c <= 0x10FFFF)
+                .ok_or_else(|| eyre::eyre!("Expected a hex codepoint like `0x4e00`, got {s:?}"))
+        };
+
+        let (start, end) = s.split_once('-').ok_or_else(|| {
+            eyre::eyre!("Expected a named glyph range or a range like `0x4e00-0x9fff`, got {s:?}")
+        })?;
+        let (start, end) = (parse_codepoint(start)?, parse_codepoint(end)?);
+        if start > end {
+            eyre::bail!("Glyph range {s:?} starts after it ends");
+        }
+
+        Ok(Self::Custom(start, end))
+    }
+}
+
+impl fmt::Display for GlyphRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(start, end) => write!(f, "{start:#06x}-{end:#06x}"),
+            named => {
+                let (name, _) = Self::NAMED
+                    .iter()
+                    .find(|(_, range)| range == named)
+                    .expect("every named range should be listed");
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+/// Every [`HudPosition`] in a stable order, e.g. for populating a dropdown.
+pub fn all_positions() -> Vec<HudPosition> {
+    HudPosition::iter().collect()
+}
+
 impl MangoHudConfig {
     pub fn new() -> Self {
         Self {
@@ -301,48 +679,889 @@ impl MangoHudConfig {
             font_scale: 1.0,
             font_scale_media_player: 0.55,
             media_player_format: "{title};{artist};{album}".into(),
-            benchmark_percentiles: "97+AVG".into(),
+            benchmark_percentiles: vec!["97".into(), "AVG".into()],
             cellpadding_y: -0.085,
             table_columns: 3,
             text_outline_thickness: 1.5,
+            toggle_hud: Keybind::new([Modifier::ShiftR], Some("F12".into())),
+            toggle_hud_position: Keybind::new([Modifier::ShiftR], Some("F11".into())),
+            toggle_fps_limit: Keybind::new([Modifier::ShiftL], Some("F1".into())),
+            toggle_logging: Keybind::new([Modifier::ShiftL], Some("F2".into())),
+            upload_log: Keybind::new([Modifier::ShiftL], Some("F3".into())),
+            reload_cfg: Keybind::new([Modifier::ShiftL], Some("F4".into())),
             ..Default::default()
         }
     }
+
+    /// `log_duration` in the unit MangoHud expects, sub-second precision is truncated.
+    pub fn log_duration_secs(&self) -> u64 {
+        self.log_duration.as_secs()
+    }
+
+    /// `log_interval` in the unit MangoHud expects.
+    pub fn log_interval_ms(&self) -> u128 {
+        self.log_interval.as_millis()
+    }
+
+    /// Enables logging on startup, writing logs to `folder`, or [`default_output_folder`] if `None`.
+    /// The folder is created if needed.
+    pub fn enable_logging(
+        &mut self,
+        folder: Option<&Path>,
+        duration: Duration,
+        interval: Duration,
+    ) -> Result<()> {
+        let folder = match folder {
+            Some(folder) => folder.to_path_buf(),
+            None => default_output_folder()
+                .ok_or_else(|| eyre::eyre!("Couldn't resolve the default output folder"))?,
+        };
+        fs_err::create_dir_all(&folder)?;
+
+        self.autostart_log = true;
+        self.output_folder = folder;
+        self.log_duration = duration;
+        self.log_interval = interval;
+
+        Ok(())
+    }
+
+    /// Folder logs are written to, taking MangoHud's default into account.
+    pub fn effective_output_folder(&self) -> Option<PathBuf> {
+        if self.output_folder.as_os_str().is_empty() {
+            default_output_folder()
+        } else {
+            Some(self.output_folder.clone())
+        }
+    }
+
+    /// The keybind changing the given option at runtime, e.g. `toggle_fps_limit` for `fps_limit`. \
+    /// `None` if the option has no keybind or it's unbound.
+    pub fn keybind_for_field(&self, key: &str) -> Option<&Keybind> {
+        let keybind = match key.trim() {
+            "no_display" => &self.toggle_hud,
+            "position" => &self.toggle_hud_position,
+            "fps_limit" => &self.toggle_fps_limit,
+            "autostart_log" => &self.toggle_logging,
+            "permit_upload" => &self.upload_log,
+            _ => return None,
+        };
+        (!keybind.is_empty()).then_some(keybind)
+    }
+
+    /// Resets everything [`MangoHudConfig::enable_logging`] sets.
+    pub fn disable_logging(&mut self) {
+        let defaults = Self::new();
+
+        self.autostart_log = defaults.autostart_log;
+        self.output_folder = defaults.output_folder;
+        self.log_duration = defaults.log_duration;
+        self.log_interval = defaults.log_interval;
+    }
+
+    /// Short human readable overview of the config, one setting per line.
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!("position: {}", self.position)];
+
+        lines.push(match self.fps_limit.as_slice() {
+            [] | [0] => "fps limit: unlimited".into(),
+            limits => format!(
+                "fps limit: {} ({})",
+                options::format_list(limits, ",", &SerializeOptions::default()),
+                self.fps_limit_method
+            ),
+        });
+
+        if self.autostart_log {
+            lines.push(match self.log_duration_secs() {
+                0 => format!("logging: every {}ms", self.log_interval_ms()),
+                secs => format!("logging: {secs}s every {}ms", self.log_interval_ms()),
+            });
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Folder logs are written to if `output_folder` isn't set, `$XDG_DATA_HOME/MangoHud`.
+pub fn default_output_folder() -> Option<PathBuf> {
+    crate::dirs::data_dir().map(|dir| dir.join("MangoHud"))
 }
 
 pub fn parse<P: AsRef<Path>>(file: P) -> Result<MangoHudConfig> {
-    let mut map = configparser::ini::Ini::new()
-        .read(fs_err::read_to_string(file)?)
-        .map_err(|e| eyre::eyre!(e))?
-        .remove("default")
-        .ok_or_else(|| eyre::eyre!("Empty default config"))?;
+    let file = file.as_ref();
+    parse_str(&fs_err::read_to_string(file)?)
+        .wrap_err_with(|| format!("Failed to parse {}", file.display()))
+}
 
+/// Parses the content of a MangoHud config file.
+pub fn parse_str(content: &str) -> Result<MangoHudConfig> {
+    parse_str_with_warnings(content).map(|(config, _)| config)
+}
+
+/// [`parse`], also returning the problems that didn't prevent parsing,
+/// such as keys used by other tools in place of MangoHud's.
+pub fn parse_with_warnings<P: AsRef<Path>>(file: P) -> Result<(MangoHudConfig, Vec<ParseWarning>)> {
+    let file = file.as_ref();
+    parse_str_with_warnings(&fs_err::read_to_string(file)?)
+        .wrap_err_with(|| format!("Failed to parse {}", file.display()))
+}
+
+/// [`parse_str`], also returning the problems that didn't prevent parsing.
+pub fn parse_str_with_warnings(content: &str) -> Result<(MangoHudConfig, Vec<ParseWarning>)> {
+    let mut config = MangoHudConfig::new();
+    let mut warnings = Vec::new();
+    config.apply_str(content, &mut warnings)?;
+    Ok((config, warnings))
+}
+
+/// [`parse_partial_str`] on the content of a file, failing only if it can't be read.
+pub fn parse_partial<P: AsRef<Path>>(
+    file: P,
+    max_errors: usize,
+) -> Result<(MangoHudConfig, Vec<ParseWarning>)> {
+    Ok(parse_partial_str(
+        &fs_err::read_to_string(file)?,
+        max_errors,
+    ))
+}
+
+/// Parses a config without stopping at the first problem, for showing every problem at once. \
+/// Lines that fail to parse are skipped and reported as a [`ParseWarning::is_error`] warning,
+/// the other lines are applied as usual. Errors past the first `max_errors` are dropped.
+pub fn parse_partial_str(content: &str, max_errors: usize) -> (MangoHudConfig, Vec<ParseWarning>) {
     let mut config = MangoHudConfig::new();
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
 
-    update_if_some(
-        &mut config.fps_limit,
-        map.remove("fps_limit")
-            .flatten()
-            .map(|s| parse_list(&s).ok())
-            .flatten(),
-    );
+    config.apply_header(content);
+    for entry in read_entries_partial(content, &mut warnings, &mut errors) {
+        let start = warnings.len();
+        if let Err(e) = config.apply_entry(&entry.key, &entry.value, &mut warnings) {
+            errors.push(ParseWarning::error(
+                &entry.key,
+                format!("Failed to parse {:?}: {e:#}", entry.value),
+                Some(entry.line),
+            ));
+        }
+        tag_line(&mut warnings[start..], entry.line);
+    }
 
-    todo!()
+    errors.sort_by_key(|error| error.line);
+    errors.truncate(max_errors);
+    warnings.extend(errors);
+    (config, warnings)
 }
 
-fn parse_list<T>(value: &str) -> Result<Vec<T>>
-where
-    T: FromStr,
-    T::Err: Into<eyre::ErrReport>,
-{
-    value
-        .split(&[',', '+'][..])
-        .map(|s| s.parse::<T>().map_err(|e| eyre::eyre!(e)))
-        .collect::<Result<_>>()
+impl MangoHudConfig {
+    /// Applies the options set in the content of a config file on top of this config.
+    fn apply_str(&mut self, content: &str, warnings: &mut Vec<ParseWarning>) -> Result<()> {
+        self.apply_header(content);
+        for Entry { key, value, line } in read_entries(content, warnings)? {
+            let start = warnings.len();
+            self.apply_entry(&key, &value, warnings)
+                .wrap_err_with(|| format!("Failed to parse `{key}={value}`"))?;
+            tag_line(&mut warnings[start..], line);
+        }
+
+        Ok(())
+    }
+
+    /// Applies the options set in a `MANGOHUD_CONFIG` string on top of this config.
+    fn apply_env(&mut self, value: &str, warnings: &mut Vec<ParseWarning>) -> Result<()> {
+        for entry in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let (key, value) = entry.split_once('=').unwrap_or((entry, "1"));
+            // Tells MangoHud to read the config file too, it's not an option of the config
+            if key.trim() == resolve::READ_CFG {
+                continue;
+            }
+            self.apply_entry(options::canonical_key(key, warnings), value, warnings)
+                .wrap_err_with(|| format!("Failed to parse `{entry}`"))?;
+        }
+
+        Ok(())
+    }
+
+    /// [`MangoHudConfig::set_by_key`], keeping unknown options in `extra` instead of failing.
+    fn apply_entry(
+        &mut self,
+        key: &str,
+        value: &str,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<()> {
+        let key = key.trim();
+        if OPTIONS.iter().any(|option| option.key == key) {
+            return self.set_by_key(key, value);
+        }
+
+        warnings.push(ParseWarning::new(key, "Unknown option, kept as is"));
+        self.extra.insert(key.into(), ExtraValue::new(value));
+        Ok(())
+    }
+
+    /// Options not known to this crate, in the order of their keys.
+    pub fn extra(&self) -> &BTreeMap<String, ExtraValue> {
+        &self.extra
+    }
+
+    /// Comment lines at the start of the config file it was parsed from, as written,
+    /// including the blank lines between them.
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    /// Lines that aren't blank or a comment already are turned into one, so the header can't set
+    /// options.
+    pub fn set_header<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, header: I) {
+        self.header.clear();
+        for line in header {
+            for line in line.as_ref().lines() {
+                self.header
+                    .push(if line.trim().is_empty() || is_comment(line) {
+                        line.trim_end().into()
+                    } else {
+                        format!("# {line}")
+                    });
+            }
+        }
+    }
+
+    /// Keeps the comments a config file starts with, unless it has none.
+    fn apply_header(&mut self, content: &str) {
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+        let mut header: Vec<String> = content
+            .lines()
+            .take_while(|line| line.trim().is_empty() || is_comment(line))
+            .map(|line| line.trim_end().into())
+            .collect();
+        while header.last().is_some_and(|line| line.is_empty()) {
+            header.pop();
+        }
+
+        if !header.is_empty() {
+            self.header = header;
+        }
+    }
+
+    /// Like `==`, but ignoring [`MangoHudConfig::extra`] and [`MangoHudConfig::header`],
+    /// e.g. for detecting changes made in a UI that only knows about the recognized options.
+    pub fn eq_known(&self, other: &Self) -> bool {
+        let mut this = self.clone();
+        this.extra = other.extra.clone();
+        this.header = other.header.clone();
+        this == *other
+    }
+}
+
+/// An entry of a config file.
+struct Entry {
+    key: String,
+    value: String,
+    /// Counted from 1.
+    line: usize,
+}
+
+/// Returns the `key=value` entries of a config file, in order, with aliased keys resolved.
+/// Bare keys are read as `key=1`. Fails on the first problem found by [`read_entries_partial`].
+fn read_entries(content: &str, warnings: &mut Vec<ParseWarning>) -> Result<Vec<Entry>> {
+    let mut errors = Vec::new();
+    let entries = read_entries_partial(content, warnings, &mut errors);
+    match errors.into_iter().next() {
+        Some(error) => Err(eyre::eyre!("{error}")),
+        None => Ok(entries),
+    }
+}
+
+/// [`read_entries`], collecting problems into `errors` and dropping the entries they're about.
+///
+/// MangoHud configs have no sections, but ones copied from examples are sometimes put under a
+/// header like `[MangoHud]`. The entries of every section are merged, unless they disagree.
+fn read_entries_partial(
+    content: &str,
+    warnings: &mut Vec<ParseWarning>,
+    errors: &mut Vec<ParseWarning>,
+) -> Vec<Entry> {
+    let content = preprocess(content, errors);
+
+    // Case-sensitive, so unknown options are written back as they were spelled
+    let mut ini = configparser::ini::Ini::new_cs();
+    // Comments are already stripped by `preprocess`
+    ini.set_comment_symbols(&[]);
+
+    let sections = match ini.read(content.clone()) {
+        Ok(sections) => sections,
+        Err(e) => {
+            // `configparser` counts lines from 0
+            let error = match e
+                .strip_prefix("line ")
+                .and_then(|e| e.split_once(": "))
+                .and_then(|(line, e)| Some((line.parse::<usize>().ok()?, e)))
+            {
+                Some((line, e)) => ParseWarning::error("", e, Some(line + 1)),
+                None => ParseWarning::error("", e, None),
+            };
+            errors.push(error);
+            return Vec::new();
+        }
+    };
+
+    // `configparser` doesn't keep track of lines, the last line setting a key is the one it keeps
+    let mut lines = HashMap::new();
+    for (i, line) in content.lines().enumerate() {
+        if let Some(key) = line
+            .split('=')
+            .next()
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+        {
+            lines.insert(key.to_string(), i + 1);
+        }
+    }
+
+    let mut entries: Vec<(Entry, &str)> = Vec::new();
+    for (section, map) in &sections {
+        for (key, value) in map {
+            // Bare keys enable the option, like in MANGOHUD_CONFIG
+            let value = value.as_deref().unwrap_or("1");
+            let line = lines.get(key.as_str()).copied().unwrap_or_default();
+            // Known options are matched regardless of case
+            let lowercase = key.to_lowercase();
+            let key = if options::is_known_key(&lowercase) {
+                &lowercase
+            } else {
+                key
+            };
+            let start = warnings.len();
+            let key = options::canonical_key(key, warnings);
+            tag_line(&mut warnings[start..], line);
+
+            match entries.iter().find(|(entry, _)| entry.key == key) {
+                Some((other, other_section)) if other.value != value => {
+                    errors.push(ParseWarning::error(
+                        key,
+                        format!(
+                            "Set to {:?} {} but to {value:?} {}",
+                            other.value,
+                            describe_section(other_section),
+                            describe_section(section)
+                        ),
+                        Some(line),
+                    ));
+                }
+                Some(_) => {}
+                None => entries.push((
+                    Entry {
+                        key: key.into(),
+                        value: value.into(),
+                        line,
+                    },
+                    section,
+                )),
+            }
+        }
+    }
+
+    entries.into_iter().map(|(entry, _)| entry).collect()
+}
+
+/// Attributes warnings without a line to the given one.
+fn tag_line(warnings: &mut [ParseWarning], line: usize) {
+    for warning in warnings {
+        warning.line.get_or_insert(line);
+    }
+}
+
+fn describe_section(section: &str) -> String {
+    if section == "default" {
+        "outside of any section".into()
+    } else {
+        format!("under [{section}]")
+    }
+}
+
+/// Whole-line comments start with `#`, `;` or `//`.
+fn is_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with(['#', ';']) || line.starts_with("//")
+}
+
+/// Prepares a config for `configparser`:
+/// - Drops comment lines, also ones between continued lines. Only whole-line comments are
+///   supported, since values like `media_player_format={title};{artist}` or `exec` commands may
+///   contain comment symbols.
+/// - Joins lines ending with `\` with the line after them, dropping the `\`, the newline and
+///   the next line's leading whitespace. Only `exec` may span multiple lines, since it's the only
+///   option taking a shell command.
+/// - Reports lines that aren't a `key=value`, a bare `key` or a section header to `errors`,
+///   and drops them.
+///
+/// Dropped and joined lines are replaced by empty ones, so line numbers stay the same.
+fn preprocess(content: &str, errors: &mut Vec<ParseWarning>) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut continued_key: Option<&str> = None;
+    let mut joined_lines = 0;
+    // Left by some editors on Windows
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    for (i, line) in content.lines().enumerate() {
+        if is_comment(line) {
+            // Between continued lines, the comment is dropped without ending the joined line
+            if continued_key.is_some() {
+                joined_lines += 1;
+            } else {
+                out.push('\n');
+            }
+            continue;
+        }
+
+        let (key, line) = match continued_key {
+            Some(key) => (key, line.trim_start()),
+            None => {
+                let key = line.split('=').next().unwrap_or_default().trim();
+                let is_key = key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                let error = if key.is_empty() && line.contains('=') {
+                    Some("Missing the key before `=`".into())
+                } else if !is_key && !key.starts_with('[') {
+                    Some(format!(
+                        "Expected `key=value` or a bare `key`, got {line:?}"
+                    ))
+                } else {
+                    None
+                };
+                if let Some(error) = error {
+                    errors.push(ParseWarning::error("", error, Some(i + 1)));
+                    out.push('\n');
+                    continue;
+                }
+                (key, line)
+            }
+        };
+
+        match line.strip_suffix('\\') {
+            Some(line) => {
+                if key != "exec" {
+                    errors.push(ParseWarning::error(
+                        key,
+                        "Can't be continued onto the next line, only `exec` can",
+                        Some(i + 1),
+                    ));
+                    out.push('\n');
+                    continue;
+                }
+                out.push_str(line);
+                continued_key = Some(key);
+                joined_lines += 1;
+            }
+            None => {
+                out.push_str(line);
+                out.push('\n');
+                for _ in 0..joined_lines {
+                    out.push('\n');
+                }
+                continued_key = None;
+                joined_lines = 0;
+            }
+        }
+    }
+
+    out
+}
+
+/// Parses the `MANGOHUD_CONFIG` environment variable format, e.g. `fps,fps_limit=30+60`. \
+/// Bare keys enable the option. `read_cfg` is skipped, as it's only a directive for reading the
+/// config file as well, see [`resolve()`].
+pub fn parse_env(value: &str) -> Result<MangoHudConfig> {
+    let mut config = MangoHudConfig::new();
+    config.apply_env(value, &mut Vec::new())?;
+    Ok(config)
 }
 
-fn update_if_some<T>(modify: &mut T, with: Option<T>) {
-    if let Some(with) = with {
-        *modify = with;
+impl MangoHudConfig {
+    /// Same as [`parse_env`]. Entries are applied through the same per-option parsing as config
+    /// files, so both formats accept the same values.
+    pub fn from_env_string(value: &str) -> Result<Self> {
+        parse_env(value)
+    }
+}
+
+/// Parses the `MANGOHUD_CONFIG` assignment out of an environment file,
+/// such as the ones used by systemd. Unrelated lines are ignored.
+pub fn parse_env_file<P: AsRef<Path>>(file: P) -> Result<MangoHudConfig> {
+    let file = file.as_ref();
+    let content = fs_err::read_to_string(file)?;
+
+    // Later assignments win, like they would in a shell.
+    let value = content
+        .lines()
+        .rev()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            line.strip_prefix("MANGOHUD_CONFIG=")
+        })
+        .next()
+        .ok_or_else(|| eyre::eyre!("No MANGOHUD_CONFIG assignment in {}", file.display()))?;
+
+    parse_env(value.trim_matches(|c| c == '"' || c == '\''))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_is_the_defaults() {
+        assert_eq!(parse_str("").unwrap(), MangoHudConfig::new());
+        assert_eq!(parse_str("  \n\t\n \n").unwrap(), MangoHudConfig::new());
+    }
+
+    #[test]
+    fn comments_between_continued_lines_are_dropped() {
+        let content = "exec=echo a \\\n# note\n    b\ngpu_temp=1\nalpha=x\n";
+        let (config, warnings) = parse_partial_str(content, 10);
+        assert_eq!(config.get_by_key("exec").as_deref(), Some("echo a b"));
+        assert_eq!(config.get_by_key("gpu_temp").as_deref(), Some("1"));
+        // Line numbers after the joined lines stay the same
+        assert_eq!(warnings[0].line, Some(5));
+    }
+
+    #[test]
+    fn malformed_configs_have_distinct_errors() {
+        let errors: Vec<_> = [
+            "not an option\n",
+            "=1\n",
+            "gpu_temp=1 \\\nfps=1\n",
+            "fps_limit=30\n[MangoHud]\nfps_limit=60\n",
+            "[MangoHud\n",
+        ]
+        .into_iter()
+        .map(|content| format!("{:#}", parse_str(content).unwrap_err()))
+        .collect();
+        for (i, error) in errors.iter().enumerate() {
+            assert!(!errors[..i].contains(error), "{errors:#?}");
+        }
+
+        // Only a section header is no error
+        assert_eq!(parse_str("[MangoHud]\n").unwrap(), MangoHudConfig::new());
+    }
+
+    #[test]
+    fn sections_are_merged() {
+        let config = parse_str("[MangoHud]\nfps_limit=60\ngpu_temp=1\n").unwrap();
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("60"));
+        assert_eq!(config.get_by_key("gpu_temp").as_deref(), Some("1"));
+
+        let config = parse_str("gpu_temp=1\n[MangoHud]\nfps_limit=60\ngpu_temp=1\n").unwrap();
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("60"));
+
+        assert!(parse_str("fps_limit=30\n[MangoHud]\nfps_limit=60\n").is_err());
+    }
+
+    #[test]
+    fn env_string() {
+        let config =
+            MangoHudConfig::from_env_string(" gpu_temp , gpu_stats=0, fps_limit=30+60 ,").unwrap();
+        assert_eq!(config.get_by_key("gpu_temp").as_deref(), Some("1"));
+        assert_eq!(config.get_by_key("gpu_stats").as_deref(), Some("0"));
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("30,60"));
+        assert_eq!(
+            config,
+            parse_str("gpu_temp\ngpu_stats=0\nfps_limit=30,60\n").unwrap()
+        );
+
+        assert!(MangoHudConfig::from_env_string("alpha=opaque").is_err());
+    }
+
+    #[test]
+    fn env_string_read_cfg_is_not_an_option() {
+        let config = MangoHudConfig::from_env_string("read_cfg,gpu_temp").unwrap();
+        assert!(config.extra().is_empty());
+        assert_eq!(config.to_config_string().unwrap(), "gpu_temp=1\n");
+    }
+
+    #[test]
+    fn media_player_names() {
+        assert_eq!(
+            "spotify".parse::<MediaPlayer>().unwrap(),
+            MediaPlayer::Spotify
+        );
+        assert_eq!(
+            "Spotify".parse::<MediaPlayer>().unwrap(),
+            MediaPlayer::Other("Spotify".into())
+        );
+        for player in MediaPlayer::known() {
+            assert_eq!(player.to_string().parse::<MediaPlayer>().unwrap(), player);
+        }
+
+        let config = parse_str("media_player_name=org.example.player\n").unwrap();
+        assert_eq!(
+            config.media_player_name,
+            Some(MediaPlayer::Other("org.example.player".into()))
+        );
+        assert_eq!(
+            config.get_by_key("media_player_name").as_deref(),
+            Some("org.example.player")
+        );
+        assert!(parse_str("media_player_name=\n")
+            .unwrap()
+            .media_player_name
+            .is_none());
+    }
+
+    #[test]
+    fn keybind_round_trips() {
+        let keybind: Keybind = "Shift_L+F12".parse().unwrap();
+        assert_eq!(keybind.modifiers(), [Modifier::ShiftL]);
+        assert_eq!(keybind.key(), Some("F12"));
+        assert_eq!(keybind.to_string(), "Shift_L+F12");
+
+        for value in ["Control_R+F1", "Alt_L+Alt_R+Home", "Shift_R+F12", "Super_L"] {
+            assert_eq!(value.parse::<Keybind>().unwrap().to_string(), value);
+        }
+        assert!("".parse::<Keybind>().unwrap().is_empty());
+
+        assert!("Shift_L+F1+F2".parse::<Keybind>().is_err());
+        assert!("Shift_L++F1".parse::<Keybind>().is_err());
+    }
+
+    #[test]
+    fn keybind_modifier_order_is_canonical() {
+        let a: Keybind = "Shift_L+Ctrl_L+F12".parse().unwrap();
+        let b: Keybind = "Ctrl_L+Shift_L+F12".parse().unwrap();
+        let c: Keybind = "Control_L+Shift_L+F12".parse().unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert_eq!(a.to_string(), "Control_L+Shift_L+F12");
+        assert_eq!(b.to_string(), a.to_string());
+    }
+
+    #[test]
+    fn keybind_modifier_without_side_suggests_one() {
+        let error = "Ctrl+F12".parse::<Keybind>().unwrap_err().to_string();
+        assert!(error.contains("`Control_L`"), "{error}");
+        let error = "shift+F12".parse::<Keybind>().unwrap_err().to_string();
+        assert!(error.contains("`Shift_L`"), "{error}");
+    }
+
+    #[test]
+    fn keybind_modifiers_ignore_case() {
+        let keybind: Keybind = "shift_l+F1".parse().unwrap();
+        assert_eq!(keybind.modifiers(), [Modifier::ShiftL]);
+        assert_eq!(keybind.to_string(), "Shift_L+F1");
+        assert_eq!(
+            "CTRL_R+alt_l+F2".parse::<Keybind>().unwrap().to_string(),
+            "Control_R+Alt_L+F2"
+        );
+    }
+
+    #[test]
+    fn parse_partial_collects_errors() {
+        let content = "fps_limit=60\nalpha=opaque\ngpu_temp=1\n=1\nvsync=7\n";
+        let (config, warnings) = parse_partial_str(content, 10);
+        let errors: Vec<_> = warnings.iter().filter(|warning| warning.is_error).collect();
+        let lines: Vec<_> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, [Some(2), Some(4), Some(5)]);
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("60"));
+        assert_eq!(config.get_by_key("gpu_temp").as_deref(), Some("1"));
+
+        let (_, warnings) = parse_partial_str(content, 2);
+        assert_eq!(
+            warnings.iter().filter(|warning| warning.is_error).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn fps_limit_method_numbers() {
+        for (value, method) in [
+            ("early", FpsLimitMethod::Early),
+            ("0", FpsLimitMethod::Early),
+            ("late", FpsLimitMethod::Late),
+            ("1", FpsLimitMethod::Late),
+        ] {
+            let mut config = MangoHudConfig::new();
+            config.set_by_key("fps_limit_method", value).unwrap();
+            assert_eq!(config.fps_limit_method, method);
+            assert_eq!(
+                config.get_by_key("fps_limit_method"),
+                Some(method.to_string())
+            );
+        }
+        assert_eq!(FpsLimitMethod::Early.to_string(), "early");
+    }
+
+    #[test]
+    fn vsync_names() {
+        for value in ["2", "mailbox", "Mailbox"] {
+            let config = parse_str(&format!("vsync={value}\n")).unwrap();
+            assert_eq!(config.vsync, Some(VSync::Mailbox));
+            assert_eq!(config.get_by_key("vsync").as_deref(), Some("2"));
+        }
+    }
+
+    #[test]
+    fn fcat_options() {
+        let config = parse_str("fcat=1\nfcat_screen_edge=2\nfcat_overlay_width=48\n").unwrap();
+        assert!(config.fcat);
+        assert_eq!(config.fcat_screen_edge, FcatOverlayEdge::Right);
+        assert_eq!(config.fcat_overlay_width, 48);
+        assert!(config.validate().is_ok());
+
+        let mut config = parse_str("fcat_overlay_width=0\n").unwrap();
+        let errors = config.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|error| error.key == "fcat_overlay_width" && !error.is_warning()));
+
+        config.fcat_overlay_width = 48;
+        let lints: Vec<_> = config.lint().into_iter().map(|lint| lint.key).collect();
+        assert_eq!(lints, ["fcat_overlay_width"]);
+    }
+
+    #[test]
+    fn eq_known_ignores_unknown_keys() {
+        let a = parse_str("gpu_temp=1\nnew_option=1\n").unwrap();
+        let b = parse_str("gpu_temp=1\nnew_option=2\n").unwrap();
+        assert!(a.eq_known(&b));
+        assert_ne!(a, b);
+
+        let c = parse_str("gpu_temp=0\nnew_option=1\n").unwrap();
+        assert!(!a.eq_known(&c));
+    }
+
+    #[test]
+    fn edits_keep_unknown_keys_and_leading_comments() {
+        let content = "# My config\n// for MangoHud 0.7\n\nfps_limit=60\nnew_option=x\n";
+        let mut config = parse_str(content).unwrap();
+        assert_eq!(config.header(), ["# My config", "// for MangoHud 0.7"]);
+
+        config.set_by_key("gpu_temp", "1").unwrap();
+        assert_eq!(
+            config.to_config_string().unwrap(),
+            "# My config\n// for MangoHud 0.7\nfps_limit=60\ngpu_temp=1\nnew_option=x\n"
+        );
+    }
+
+    #[test]
+    fn unknown_keys_keep_their_case() {
+        let config = parse_str("NewOption=Hello\nFPS_Limit=30\n").unwrap();
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("30"));
+        assert!(config.extra().contains_key("NewOption"));
+
+        let written = config.to_config_string().unwrap();
+        assert!(written.contains("NewOption=Hello\n"), "{written}");
+        assert!(written.contains("fps_limit=30\n"), "{written}");
+    }
+
+    #[test]
+    fn keybind_for_field_defaults_and_unbound() {
+        let mut config = MangoHudConfig::new();
+        let keybind = config.keybind_for_field("fps_limit").unwrap();
+        assert_eq!(keybind.to_string(), "Shift_L+F1");
+        assert_eq!(
+            config.keybind_for_field("no_display").unwrap().to_string(),
+            "Shift_R+F12"
+        );
+        assert!(config.keybind_for_field("gpu_temp").is_none());
+
+        config.set_by_key("toggle_fps_limit", "").unwrap();
+        assert!(config.keybind_for_field("fps_limit").is_none());
+    }
+
+    #[test]
+    fn logging_durations_in_config_units() {
+        let mut config = parse_str("autostart_log=1\nlog_duration=60\nlog_interval=100\n").unwrap();
+        assert_eq!(config.log_duration_secs(), 60);
+        assert_eq!(config.log_interval_ms(), 100);
+        assert!(config.summary().contains("logging: 60s every 100ms"));
+        assert!(config.lint().iter().all(|lint| lint.key != "log_duration"));
+
+        config.log_duration = Duration::from_millis(1500);
+        assert_eq!(config.log_duration_secs(), 1);
+        let lint = config
+            .lint()
+            .into_iter()
+            .find(|lint| lint.key == "log_duration")
+            .unwrap();
+        assert!(lint.message.contains("truncated to 1s"), "{lint}");
+    }
+
+    #[test]
+    fn env_file_ignores_unrelated_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mangohud.env");
+        fs_err::write(
+            &path,
+            "# Set by the distro\nDXVK_HUD=1\nMANGOHUD_CONFIG=\"gpu_temp,fps_limit=30+60\"\n",
+        )
+        .unwrap();
+
+        let config = parse_env_file(&path).unwrap();
+        assert_eq!(config, parse_env("gpu_temp,fps_limit=30+60").unwrap());
+        assert!(config.extra().is_empty());
+
+        fs_err::write(&path, "DXVK_HUD=1\n").unwrap();
+        assert!(parse_env_file(&path).is_err());
+    }
+
+    #[test]
+    fn enable_logging_sets_every_logging_option() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("logs");
+        let mut config = MangoHudConfig::new();
+        config
+            .enable_logging(
+                Some(&folder),
+                Duration::from_secs(30),
+                Duration::from_millis(100),
+            )
+            .unwrap();
+
+        assert!(config.autostart_log);
+        assert_eq!(config.output_folder, folder);
+        assert_eq!(config.log_duration_secs(), 30);
+        assert_eq!(config.log_interval_ms(), 100);
+        assert!(folder.is_dir());
+
+        config.disable_logging();
+        assert_eq!(config, MangoHudConfig::new());
+    }
+
+    #[test]
+    fn all_positions_in_order() {
+        use HudPosition::*;
+        assert_eq!(
+            all_positions(),
+            [
+                TopLeft,
+                TopCenter,
+                TopRight,
+                MiddleLeft,
+                MiddleRight,
+                BottomLeft,
+                BottomRight
+            ]
+        );
+        let labels: Vec<_> = all_positions().iter().map(HudPosition::label).collect();
+        assert_eq!(labels[1], "Top Center");
+    }
+
+    #[test]
+    fn glyph_ranges_mix_names_and_codepoints() {
+        let config = parse_str("font_glyph_ranges=korean,0x0400-0x04FF\n").unwrap();
+        assert_eq!(
+            config.font_glyph_ranges,
+            [GlyphRange::Korean, GlyphRange::Custom(0x0400, 0x04ff)]
+        );
+        assert_eq!(
+            config.get_by_key("font_glyph_ranges").as_deref(),
+            Some("korean,0x0400-0x04ff")
+        );
+
+        assert!(parse_str("font_glyph_ranges=0x04FF-0x0400\n").is_err());
+        assert!(parse_str("font_glyph_ranges=0x0400-0xzz\n").is_err());
     }
 }
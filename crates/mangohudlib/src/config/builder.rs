@@ -0,0 +1,97 @@
+//! Assembling configs in code, for frontends that don't start from a config file.
+
+use std::path::PathBuf;
+
+use rgb::RGB8;
+
+use super::{HudPosition, HudPreset, Keybind, MangoHudConfig, ValidationError};
+
+/// Builds a [`MangoHudConfig`] starting from [`MangoHudConfig::new`], e.g.
+/// `MangoHudConfig::builder().fps_limit(vec![60]).gpu_temp(true).build()`. \
+/// Options without a setter can be set through [`MangoHudConfigBuilder::set`].
+#[derive(Debug, Clone)]
+pub struct MangoHudConfigBuilder {
+    config: MangoHudConfig,
+}
+
+/// Adds a setter per field, named after the option.
+macro_rules! setters {
+    ($($field:ident: $ty:ty,)*) => {$(
+        pub fn $field(mut self, value: $ty) -> Self {
+            self.config.$field = value;
+            self
+        }
+    )*};
+}
+
+impl MangoHudConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: MangoHudConfig::new(),
+        }
+    }
+
+    setters! {
+        fps_limit: Vec<u16>,
+        legacy_layout: bool,
+        preset: HudPreset,
+        gpu_stats: bool,
+        gpu_temp: bool,
+        gpu_power: bool,
+        cpu_stats: bool,
+        cpu_temp: bool,
+        cpu_power: bool,
+        vram: bool,
+        ram: bool,
+        fps: bool,
+        frametime: bool,
+        frame_timing: bool,
+        font_size: f32,
+        font_file: PathBuf,
+        position: HudPosition,
+        round_corners: f32,
+        horizontal: bool,
+        no_display: bool,
+        background_alpha: f32,
+        alpha: f32,
+        text_color: RGB8,
+        gpu_color: RGB8,
+        cpu_color: RGB8,
+        background_color: RGB8,
+        toggle_hud: Keybind,
+    }
+
+    /// Sets any option from its config representation, see [`MangoHudConfig::set_by_key`].
+    pub fn set(mut self, key: &str, value: &str) -> eyre::Result<Self> {
+        self.config.set_by_key(key, value)?;
+        Ok(self)
+    }
+
+    /// Returns the config, or every value that is out of range. \
+    /// [Warnings](ValidationError::is_warning), like a setting without effect given the others,
+    /// don't fail the build, see [`MangoHudConfig::validate`] for those.
+    pub fn build(self) -> Result<MangoHudConfig, Vec<ValidationError>> {
+        let errors: Vec<_> = match self.config.validate() {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.into_iter().filter(|e| !e.is_warning()).collect(),
+        };
+
+        if errors.is_empty() {
+            Ok(self.config)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Default for MangoHudConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MangoHudConfig {
+    pub fn builder() -> MangoHudConfigBuilder {
+        MangoHudConfigBuilder::new()
+    }
+}
@@ -0,0 +1,191 @@
+//! Description of the values each option accepts, for building an input widget per option.
+
+use std::{ops::RangeInclusive, path::PathBuf, time::Duration};
+
+use rgb::RGB8;
+use strum::IntoEnumIterator;
+
+use super::*;
+
+/// Ranges of the options MangoHud only accepts part of their type's values for. \
+/// Parsing, [`MangoHudConfig::validate`] and [`constraints_for`] all read them from here.
+pub(super) const PICMIP_RANGE: RangeInclusive<i64> = -16..=16;
+pub(super) const AF_RANGE: RangeInclusive<i64> = 0..=16;
+// MangoHud clamps alphas itself, but anything outside of this is likely meant as a percentage
+pub(super) const ALPHA_RANGE: RangeInclusive<f32> = 0.0..=1.0;
+pub(super) const TEXT_OUTLINE_THICKNESS_RANGE: RangeInclusive<f32> = 0.0..=10.0;
+// Horizontal layouts give every item its own column, so the cap is well above the default 3.
+pub(super) const TABLE_COLUMNS_RANGE: RangeInclusive<u8> = 1..=20;
+pub(super) const FCAT_OVERLAY_WIDTH_RANGE: RangeInclusive<u16> = 1..=u16::MAX;
+
+/// The values an option accepts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constraint {
+    Bool,
+    IntRange(RangeInclusive<i64>),
+    FloatRange(RangeInclusive<f32>),
+    /// One of the given values, as written in the config.
+    Variants(Vec<String>),
+    /// A hex color like `ff0000`, or a color name.
+    Color,
+    /// A [`Keybind`] like `Shift_R+F12`.
+    Keybind,
+    Path,
+    String,
+    /// Values joined with [`OptionMeta::separator`], `len` is set if the list has a fixed length.
+    List {
+        item: Box<Constraint>,
+        len: Option<usize>,
+    },
+}
+
+/// Accepted values of an option, see [`OptionMeta::constraint`].
+pub fn constraints_for(key: &str) -> Option<Constraint> {
+    OPTIONS
+        .iter()
+        .find(|option| option.key == key)
+        .map(OptionMeta::constraint)
+}
+
+pub(super) fn int_range<T: Copy + Into<i64>>(range: RangeInclusive<T>) -> Constraint {
+    Constraint::IntRange((*range.start()).into()..=(*range.end()).into())
+}
+
+/// The values a field's type can hold, narrowed per option by [`OptionMeta::constraint`].
+pub(super) trait FieldConstraint {
+    fn constraint() -> Constraint;
+}
+
+/// Infers the field's type from an accessor, so the registry doesn't have to spell it out.
+pub(super) fn constraint_of<T: FieldConstraint>(_: impl Fn(&MangoHudConfig) -> &T) -> Constraint {
+    T::constraint()
+}
+
+macro_rules! impl_int_constraint {
+    ($($ty:ty),*) => {$(
+        impl FieldConstraint for $ty {
+            fn constraint() -> Constraint {
+                int_range(<$ty>::MIN..=<$ty>::MAX)
+            }
+        }
+    )*};
+}
+
+impl_int_constraint!(u8, u16, i8);
+
+macro_rules! impl_variants_constraint {
+    ($($ty:ty),*) => {$(
+        impl FieldConstraint for $ty {
+            fn constraint() -> Constraint {
+                Constraint::Variants(<$ty>::iter().map(|variant| variant.to_string()).collect())
+            }
+        }
+    )*};
+}
+
+impl_variants_constraint!(
+    FpsLimitMethod,
+    VSync,
+    HudPreset,
+    HudPosition,
+    FcatOverlayEdge
+);
+
+impl FieldConstraint for bool {
+    fn constraint() -> Constraint {
+        Constraint::Bool
+    }
+}
+
+impl FieldConstraint for f32 {
+    fn constraint() -> Constraint {
+        Constraint::FloatRange(f32::MIN..=f32::MAX)
+    }
+}
+
+impl FieldConstraint for String {
+    fn constraint() -> Constraint {
+        Constraint::String
+    }
+}
+
+/// Named ranges are listed in [`GlyphRange`], but any range of codepoints is accepted as well.
+impl FieldConstraint for GlyphRange {
+    fn constraint() -> Constraint {
+        Constraint::String
+    }
+}
+
+/// Well-known players are listed by [`MediaPlayer::known`], but any D-Bus name is accepted.
+impl FieldConstraint for MediaPlayer {
+    fn constraint() -> Constraint {
+        Constraint::String
+    }
+}
+
+impl FieldConstraint for PathBuf {
+    fn constraint() -> Constraint {
+        Constraint::Path
+    }
+}
+
+impl FieldConstraint for Keybind {
+    fn constraint() -> Constraint {
+        Constraint::Keybind
+    }
+}
+
+impl FieldConstraint for RGB8 {
+    fn constraint() -> Constraint {
+        Constraint::Color
+    }
+}
+
+/// Durations are written as a whole number of seconds or milliseconds, depending on the option.
+impl FieldConstraint for Duration {
+    fn constraint() -> Constraint {
+        Constraint::IntRange(0..=i64::MAX)
+    }
+}
+
+/// Unset is represented by leaving the option out, so the constraint is the one of the value.
+impl<T: FieldConstraint> FieldConstraint for Option<T> {
+    fn constraint() -> Constraint {
+        T::constraint()
+    }
+}
+
+impl<T: FieldConstraint> FieldConstraint for Vec<T> {
+    fn constraint() -> Constraint {
+        Constraint::List {
+            item: Box::new(T::constraint()),
+            len: None,
+        }
+    }
+}
+
+impl<T: FieldConstraint, const N: usize> FieldConstraint for [T; N] {
+    fn constraint() -> Constraint {
+        Constraint::List {
+            item: Box::new(T::constraint()),
+            len: Some(N),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn af_range_and_position_variants() {
+        assert_eq!(constraints_for("af"), Some(Constraint::IntRange(0..=16)));
+        assert_eq!(
+            constraints_for("position"),
+            Some(Constraint::Variants(
+                all_positions().iter().map(ToString::to_string).collect()
+            ))
+        );
+        assert_eq!(constraints_for("no_such_option"), None);
+    }
+}
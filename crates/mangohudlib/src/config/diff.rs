@@ -0,0 +1,83 @@
+//! Comparing configs option by option.
+
+use std::path::Path;
+
+use eyre::Result;
+
+use super::{parse, MangoHudConfig, OPTIONS};
+
+/// An option set differently in two configs, in its MangoHud representation. \
+/// A value is `None` if the option is unset in that config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigFieldDiff {
+    pub key: &'static str,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl MangoHudConfig {
+    /// Every option set differently in this config than in `old`.
+    pub fn diff(&self, old: &MangoHudConfig) -> Vec<ConfigFieldDiff> {
+        OPTIONS
+            .iter()
+            .filter_map(|option| {
+                let diff = ConfigFieldDiff {
+                    key: option.key,
+                    old: old.get_by_key(option.key),
+                    new: self.get_by_key(option.key),
+                };
+                (diff.old != diff.new).then_some(diff)
+            })
+            .collect()
+    }
+
+    /// Every option set differently in this config than in the config file at `path`,
+    /// e.g. to list unsaved changes. A missing file counts as all defaults.
+    pub fn diff_against_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<ConfigFieldDiff>> {
+        let path = path.as_ref();
+        let saved = if path.exists() {
+            parse(path)?
+        } else {
+            MangoHudConfig::new()
+        };
+        Ok(self.diff(&saved))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_against_file_reports_unsaved_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("MangoHud.conf");
+        fs_err::write(&path, "alpha=0.5\ngpu_temp=1\n").unwrap();
+
+        let mut config = parse(&path).unwrap();
+        assert!(config.diff_against_file(&path).unwrap().is_empty());
+
+        config.set_by_key("alpha", "0.8").unwrap();
+        assert_eq!(
+            config.diff_against_file(&path).unwrap(),
+            [ConfigFieldDiff {
+                key: "alpha",
+                old: Some("0.5".into()),
+                new: Some("0.8".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_against_missing_file_uses_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("alpha", "0.8").unwrap();
+
+        let diff = config
+            .diff_against_file(dir.path().join("missing.conf"))
+            .unwrap();
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].key, "alpha");
+    }
+}
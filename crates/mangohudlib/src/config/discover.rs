@@ -0,0 +1,53 @@
+//! Finding the config file MangoHud would read.
+
+use std::{env, path::PathBuf};
+
+use eyre::Result;
+
+use super::{parse, MangoHudConfig};
+
+/// Config files MangoHud would read for the program `app`, e.g. `vkcube`, in order of preference:
+/// - `MANGOHUD_CONFIGFILE`, the only candidate if it's set.
+/// - The per-app config, `MangoHud/<app>.conf`, or `MangoHud/wine-<app>.conf` for Wine games,
+///   in `$XDG_CONFIG_HOME` then `~/.config`.
+/// - The global config, `MangoHud/MangoHud.conf`, in the same directories.
+///
+/// MangoHud reads only the most specific file that exists, the others aren't layered on top.
+pub fn config_candidates(app: Option<&str>) -> Vec<PathBuf> {
+    if let Some(file) = env::var_os("MANGOHUD_CONFIGFILE").filter(|file| !file.is_empty()) {
+        return vec![file.into()];
+    }
+
+    let dirs: Vec<_> = crate::dirs::config_dirs()
+        .into_iter()
+        .map(|dir| dir.join("MangoHud"))
+        .collect();
+    let mut names = Vec::new();
+    if let Some(app) = app {
+        names.extend([format!("{app}.conf"), format!("wine-{app}.conf")]);
+    }
+    names.push("MangoHud.conf".into());
+
+    names
+        .iter()
+        .flat_map(|name| dirs.iter().map(move |dir| dir.join(name)))
+        .collect()
+}
+
+/// The first of [`config_candidates`] that exists.
+pub fn locate_config(app: Option<&str>) -> Option<PathBuf> {
+    config_candidates(app)
+        .into_iter()
+        .find(|file| file.is_file())
+}
+
+impl MangoHudConfig {
+    /// Parses the config MangoHud would use for `app`, see [`locate_config`].
+    /// The defaults if there's no config file.
+    pub fn load(app: Option<&str>) -> Result<Self> {
+        match locate_config(app) {
+            Some(file) => parse(file),
+            None => Ok(Self::new()),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Options that aren't known to this crate, see [`MangoHudConfig::extra`].
+//!
+//! [`MangoHudConfig::extra`]: super::MangoHudConfig::extra
+
+/// An unknown option's value, as written in the config.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtraValue {
+    pub value: String,
+    /// What the value looks like, e.g. for picking a widget to edit it with.
+    pub kind: ExtraKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ExtraKind {
+    /// `0`, `1` or a bare key.
+    Bool,
+    Number,
+    Text,
+}
+
+impl ExtraValue {
+    pub fn new(value: &str) -> Self {
+        let value = value.trim();
+        let kind = match value {
+            "0" | "1" => ExtraKind::Bool,
+            _ if value.parse::<f64>().is_ok() => ExtraKind::Number,
+            _ => ExtraKind::Text,
+        };
+
+        Self {
+            value: value.into(),
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_unknown_key_is_a_bool() {
+        let config = crate::config::parse_str("new_flag\nnew_size=1.5\nnew_name=x\n").unwrap();
+        let kinds: Vec<_> = ["new_flag", "new_size", "new_name"]
+            .into_iter()
+            .map(|key| config.extra()[key].kind)
+            .collect();
+        assert_eq!(kinds, [ExtraKind::Bool, ExtraKind::Number, ExtraKind::Text]);
+    }
+}
@@ -0,0 +1,60 @@
+//! Hashing of configs that stays the same across processes.
+
+use super::{MangoHudConfig, OPTIONS};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl MangoHudConfig {
+    /// Hash of the [normalized](MangoHudConfig::normalize) values of every known option, e.g. to
+    /// tell whether a config file needs rewriting. Unknown options are ignored.
+    ///
+    /// Unlike [`std::hash::Hash`] with the default hasher, the hash doesn't change between runs,
+    /// so it can be stored. It also doesn't depend on the order options are declared in.
+    pub fn content_hash(&self) -> u64 {
+        let mut config = self.clone();
+        config.normalize();
+
+        let mut options: Vec<_> = OPTIONS.iter().collect();
+        options.sort_by_key(|option| option.key);
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for option in options {
+            let value = config.get_by_key(option.key).unwrap_or_default();
+            // The separators keep e.g. `a=bc` and `ab=c` apart
+            for bytes in [option.key.as_bytes(), b"=", value.as_bytes(), b"\n"] {
+                hash = fnv1a(hash, bytes);
+            }
+        }
+        hash
+    }
+}
+
+/// https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parse_str;
+
+    #[test]
+    fn equal_configs_hash_equal() {
+        let a = parse_str("gpu_temp=1\nfps_limit=30,60\nnew_option=1\n").unwrap();
+        let b = parse_str("[MangoHud]\nfps_limit=30,60,30\ngpu_temp\n").unwrap();
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_eq!(
+            MangoHudConfig::new().content_hash(),
+            MangoHudConfig::new().content_hash()
+        );
+
+        let c = parse_str("gpu_temp=1\nfps_limit=60,30\n").unwrap();
+        assert_ne!(a.content_hash(), c.content_hash());
+    }
+}
@@ -0,0 +1,92 @@
+//! Best-effort conversion of other overlays' configs.
+
+use super::{MangoHudConfig, ParseWarning};
+
+/// `DXVK_HUD` items and the MangoHud options showing roughly the same thing.
+/// https://github.com/doitsujin/dxvk#hud
+const DXVK_HUD_ITEMS: &[(&str, &[&str])] = &[
+    ("devinfo", &["gpu_name", "vulkan_driver"]),
+    ("fps", &["fps"]),
+    ("frametimes", &["frame_timing"]),
+    ("gpuload", &["gpu_stats"]),
+    ("memory", &["vram"]),
+    ("version", &["version"]),
+    ("api", &["engine_version"]),
+];
+
+/// Converts a `DXVK_HUD` value like `fps,gpuload` into the closest MangoHud config. \
+/// This is lossy, e.g. DXVK's draw call and pipeline counters have no MangoHud equivalent.
+pub fn from_dxvk_hud(value: &str) -> MangoHudConfig {
+    from_dxvk_hud_with_warnings(value).0
+}
+
+/// [`from_dxvk_hud`], also returning a warning for every item that couldn't be converted.
+pub fn from_dxvk_hud_with_warnings(value: &str) -> (MangoHudConfig, Vec<ParseWarning>) {
+    let value = value.trim();
+    let value = value.strip_prefix("DXVK_HUD=").unwrap_or(value);
+
+    // Only show what DXVK would
+    let mut config = MangoHudConfig::new();
+    config.gpu_stats = false;
+    config.cpu_stats = false;
+    config.fps = false;
+    config.frametime = false;
+    config.frame_timing = false;
+
+    let mut warnings = Vec::new();
+    let mut warn = |item: &str, message: String| warnings.push(ParseWarning::new(item, message));
+
+    for item in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (keys, value): (Vec<&str>, &str) = match item.split_once('=') {
+            Some(("scale", value)) => (vec!["font_scale"], value),
+            Some(("opacity", value)) => (vec!["alpha"], value),
+            Some(_) => (Vec::new(), ""),
+            // `1` is DXVK's shorthand for `devinfo,fps`
+            None if item == "1" => (vec!["gpu_name", "vulkan_driver", "fps"], "1"),
+            None if item == "full" => (
+                DXVK_HUD_ITEMS
+                    .iter()
+                    .flat_map(|(_, keys)| keys.iter().copied())
+                    .collect(),
+                "1",
+            ),
+            None => (
+                DXVK_HUD_ITEMS
+                    .iter()
+                    .find(|(name, _)| *name == item)
+                    .map(|(_, keys)| keys.to_vec())
+                    .unwrap_or_default(),
+                "1",
+            ),
+        };
+
+        if keys.is_empty() {
+            warn(item, "No MangoHud equivalent, ignored".into());
+        }
+        for key in keys {
+            if let Err(e) = config.set_by_key(key, value) {
+                warn(item, format!("Couldn't convert to `{key}`: {e}"));
+            }
+        }
+    }
+
+    (config, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dxvk_fps_and_gpuload() {
+        let (config, warnings) = from_dxvk_hud_with_warnings("DXVK_HUD=fps,gpuload,drawcalls");
+        let enabled: Vec<_> = ["fps", "gpu_stats", "cpu_stats", "frametime"]
+            .into_iter()
+            .filter(|key| config.get_by_key(key).as_deref() == Some("1"))
+            .collect();
+        assert_eq!(enabled, ["fps", "gpu_stats"]);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].key, "drawcalls");
+    }
+}
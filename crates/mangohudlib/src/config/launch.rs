@@ -0,0 +1,161 @@
+//! Environment for launching a game with MangoHud enabled.
+
+use std::{env, io::Write, path::Path};
+
+use eyre::Result;
+use tempfile::TempPath;
+
+use super::{MangoHudConfig, SerializeOptions};
+use crate::dirs;
+
+/// `MANGOHUD_CONFIG` values longer than this are written to a file instead,
+/// to keep the environment of the game, e.g. as shown by `ps`, readable.
+const MAX_ENV_CONFIG_LEN: usize = 256;
+
+/// Environment variables enabling MangoHud for a launched game, see [`MangoHudConfig::to_launch_env`].
+#[derive(Debug)]
+pub struct LaunchEnv {
+    pub vars: Vec<(String, String)>,
+    /// Deleted when this is dropped, so it has to be kept around until the game has exited.
+    config_file: Option<TempPath>,
+}
+
+impl LaunchEnv {
+    /// The file `MANGOHUD_CONFIGFILE` points to, if the config didn't fit in `MANGOHUD_CONFIG`.
+    pub fn config_file(&self) -> Option<&Path> {
+        self.config_file.as_deref()
+    }
+}
+
+impl MangoHudConfig {
+    /// Environment variables enabling MangoHud with this config for a launched game. \
+    /// Short configs are passed as `MANGOHUD_CONFIG`, longer ones or ones with values that
+    /// can't be written in it are written to a file passed as `MANGOHUD_CONFIGFILE`.
+    ///
+    /// The file is only readable by the current user, as `exec` options run commands, and is
+    /// created in `$XDG_RUNTIME_DIR`, falling back to the temp dir. \
+    /// It's deleted once the returned [`LaunchEnv`] is dropped.
+    ///
+    /// MangoHud doesn't read any config file when `MANGOHUD_CONFIG` is set,
+    /// so either way the game runs with exactly this config.
+    pub fn to_launch_env(&self) -> Result<LaunchEnv> {
+        let mut vars = vec![("MANGOHUD".into(), "1".into())];
+        let mut config_file = None;
+
+        match self
+            .to_env_string()
+            .filter(|config| config.len() <= MAX_ENV_CONFIG_LEN)
+        {
+            Some(config) => vars.push(("MANGOHUD_CONFIG".into(), config)),
+            None => {
+                // MangoHud reads one option per line, so `exec` can't be wrapped
+                let options = SerializeOptions {
+                    wrap_exec: false,
+                    ..Default::default()
+                };
+                let content = self.to_config_string_with(&options)?;
+                // Created with `O_EXCL` and mode 0600, so nobody else can swap or read it
+                let mut file = tempfile::Builder::new()
+                    .prefix("mangohud-")
+                    .suffix(".conf")
+                    .tempfile_in(dirs::runtime_dir().unwrap_or_else(env::temp_dir))?;
+                file.write_all(content.as_bytes())?;
+                let path = file.into_temp_path();
+                vars.push(("MANGOHUD_CONFIGFILE".into(), path.display().to_string()));
+                config_file = Some(path);
+            }
+        }
+
+        Ok(LaunchEnv { vars, config_file })
+    }
+
+    /// The options that differ from [`MangoHudConfig::new`] in the `MANGOHUD_CONFIG` format,
+    /// e.g. `fps_limit=30+60,gpu_temp=1`. \
+    /// `None` if a value contains a `,` or a line break, which the format has no way to escape.
+    fn to_env_string(&self) -> Option<String> {
+        let options = SerializeOptions::default();
+        let known = self.changed_options(&options).map(|(option, value)| {
+            // `,` separates the entries, so lists are joined with `+`, which MangoHud accepts too
+            match option.separator {
+                Some(",") => (option.key, value.replace(',', "+")),
+                _ => (option.key, value),
+            }
+        });
+        let extra = self
+            .extra
+            .iter()
+            .map(|(key, extra)| (key.as_str(), extra.value.clone()));
+
+        known
+            .chain(extra)
+            .map(|(key, value)| {
+                (!value.contains([',', '\n', '\r'])).then(|| format!("{key}={value}"))
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(|entries| entries.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var<'a>(env: &'a LaunchEnv, key: &str) -> Option<&'a str> {
+        env.vars
+            .iter()
+            .find(|(var, _)| var == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn small_config_is_passed_inline() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("fps_limit", "30,60").unwrap();
+
+        let env = config.to_launch_env().unwrap();
+        assert_eq!(var(&env, "MANGOHUD"), Some("1"));
+        assert_eq!(var(&env, "MANGOHUD_CONFIG"), Some("fps_limit=30+60"));
+        assert_eq!(var(&env, "MANGOHUD_CONFIGFILE"), None);
+        assert!(env.config_file().is_none());
+    }
+
+    #[test]
+    fn large_config_is_written_to_a_private_file() {
+        let mut config = MangoHudConfig::new();
+        config
+            .set_by_key("custom_text", &"a".repeat(MAX_ENV_CONFIG_LEN))
+            .unwrap();
+
+        let env = config.to_launch_env().unwrap();
+        assert_eq!(var(&env, "MANGOHUD_CONFIG"), None);
+        let path = env.config_file().unwrap().to_owned();
+        assert_eq!(var(&env, "MANGOHUD_CONFIGFILE"), path.to_str());
+        assert_eq!(
+            fs_err::read_to_string(&path).unwrap(),
+            config.to_config_string().unwrap()
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs_err::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        drop(env);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn long_exec_is_written_on_one_line() {
+        let mut config = MangoHudConfig::new();
+        let command = ["echo long"; 40].join(" ");
+        config.set_by_key("exec", &command).unwrap();
+        config.set_by_key("gpu_temp", "1").unwrap();
+
+        let env = config.to_launch_env().unwrap();
+        let content = fs_err::read_to_string(env.config_file().unwrap()).unwrap();
+        let lines: Vec<_> = content.lines().collect();
+        assert_eq!(lines, ["gpu_temp=1".to_owned(), format!("exec={command}")]);
+    }
+}
@@ -0,0 +1,290 @@
+//! Approximations of how the hud is laid out, for previews.
+
+use rgb::RGBA8;
+
+use super::{MangoHudConfig, OPTIONS};
+
+/// A row of the hud.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Metric {
+    Time,
+    Version,
+    GpuLoad,
+    CpuLoad,
+    CoreLoad,
+    Io,
+    Vram,
+    Ram,
+    ProcMem,
+    Battery,
+    GamepadBattery,
+    Fps,
+    /// The frametime text next to the fps.
+    Frametime,
+    EngineVersion,
+    GpuName,
+    VulkanDriver,
+    Arch,
+    Wine,
+    ExecName,
+    /// The frametime graph.
+    FrameTiming,
+    FrameCount,
+    FpsLimit,
+    Resolution,
+    Gamemode,
+    VkBasalt,
+    Throttling,
+    MediaPlayer,
+    CustomTextCenter,
+    CustomText,
+}
+
+impl MangoHudConfig {
+    /// Every enabled row of the hud, from top to bottom.
+    ///
+    /// With `legacy_layout` this is MangoHud's fixed order. Otherwise MangoHud draws the rows in
+    /// the order they're listed in the config, which isn't kept when parsing, so the order of
+    /// MangoHud's example config is used instead.
+    pub fn enabled_metrics(&self) -> Vec<Metric> {
+        let legacy = [
+            (Metric::Time, self.time),
+            (Metric::Version, self.version),
+            (Metric::GpuLoad, self.gpu_stats),
+            (Metric::CpuLoad, self.cpu_stats),
+            (Metric::CoreLoad, self.core_load),
+            (Metric::Io, self.io_read || self.io_write),
+            (Metric::Vram, self.vram),
+            (Metric::Ram, self.ram || self.swap),
+            (Metric::ProcMem, self.procmem),
+            (Metric::Battery, self.battery),
+            (Metric::GamepadBattery, self.gamepad_battery),
+            (Metric::Fps, self.fps),
+            (Metric::Frametime, self.frametime),
+            (Metric::EngineVersion, self.engine_version),
+            (Metric::GpuName, self.gpu_name),
+            (Metric::VulkanDriver, self.vulkan_driver),
+            (Metric::Arch, self.arch),
+            (Metric::Wine, self.wine),
+            (Metric::ExecName, self.exec_name),
+            (Metric::FrameTiming, self.frame_timing),
+            (Metric::FrameCount, self.frame_count),
+            (Metric::FpsLimit, self.show_fps_limit),
+            (Metric::Resolution, self.resolution),
+            (Metric::Gamemode, self.gamemode),
+            (Metric::VkBasalt, self.vkbasalt),
+            (Metric::Throttling, self.throttling_status),
+            (Metric::MediaPlayer, self.media_player),
+            (
+                Metric::CustomTextCenter,
+                !self.custom_text_center.is_empty(),
+            ),
+            (Metric::CustomText, !self.custom_text.is_empty()),
+        ];
+
+        let mut metrics: Vec<_> = legacy
+            .into_iter()
+            .filter_map(|(metric, enabled)| enabled.then_some(metric))
+            .collect();
+        if !self.legacy_layout {
+            metrics.sort_by_key(|&metric| example_config_position(metric));
+        }
+        metrics
+    }
+
+    /// Rough width and height in pixels the hud will occupy. \
+    /// This isn't exact, but responds to the same options MangoHud's layout does.
+    pub fn estimated_size(&self) -> (f32, f32) {
+        let font_size = self.font_size * self.font_scale;
+        let padding = if self.hud_compact || self.hud_no_margin {
+            0.0
+        } else {
+            font_size * 0.5
+        };
+        let row_height = font_size * (1.0 + self.cellpadding_y.max(-0.5));
+        let column_width = font_size * 4.5;
+        let graph_height = if self.frame_timing {
+            font_size * 3.0
+        } else {
+            0.0
+        };
+
+        // The frametime is drawn next to the fps and the graph is sized separately
+        let rows = self
+            .enabled_metrics()
+            .into_iter()
+            .filter(|metric| !matches!(metric, Metric::Frametime | Metric::FrameTiming))
+            .count() as f32;
+
+        let (width, height) = if self.horizontal {
+            (
+                rows * column_width + graph_height * 2.0,
+                row_height.max(graph_height),
+            )
+        } else {
+            let row_height = if self.hud_compact {
+                row_height * 0.8
+            } else {
+                row_height
+            };
+            (
+                f32::from(self.table_columns.max(1)) * column_width,
+                rows * row_height + graph_height,
+            )
+        };
+
+        let width = if self.width > 0.0 { self.width } else { width };
+
+        (width + padding * 2.0, height + padding * 2.0)
+    }
+
+    /// `(offset_x, offset_y)`, the hud's offset in pixels from the corner or edge of `position`.
+    pub fn hud_offset(&self) -> (f32, f32) {
+        (self.offset_x, self.offset_y)
+    }
+
+    pub fn set_hud_offset(&mut self, x: f32, y: f32) {
+        self.offset_x = x;
+        self.offset_y = y;
+    }
+
+    /// `(width, height)` of the hud in pixels. A `width` of 0 sizes the hud to its content.
+    pub fn hud_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    pub fn set_hud_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// `background_color` with `background_alpha` applied, as the renderer would draw it.
+    pub fn effective_background(&self) -> RGBA8 {
+        let alpha = self.background_alpha.clamp(0.0, 1.0);
+        let c = self.background_color;
+        RGBA8::new(c.r, c.g, c.b, (alpha * 255.0).round() as u8)
+    }
+
+    /// `font_size` as it would be rendered on a display with the given UI scale. \
+    /// With `no_small_font` the text is never rendered smaller than `font_size`.
+    pub fn scaled_font_size(&self, ui_scale: f32) -> f32 {
+        let size = self.font_size * self.font_scale * ui_scale;
+        if self.no_small_font {
+            size.max(self.font_size)
+        } else {
+            size
+        }
+    }
+}
+
+/// Where the option enabling `metric` is listed in MangoHud's example config,
+/// see [`OPTIONS`](super::OPTIONS).
+fn example_config_position(metric: Metric) -> usize {
+    let key = match metric {
+        Metric::Time => "time",
+        Metric::Version => "version",
+        Metric::GpuLoad => "gpu_stats",
+        Metric::CpuLoad => "cpu_stats",
+        Metric::CoreLoad => "core_load",
+        Metric::Io => "io_read",
+        Metric::Vram => "vram",
+        Metric::Ram => "ram",
+        Metric::ProcMem => "procmem",
+        Metric::Battery => "battery",
+        Metric::GamepadBattery => "gamepad_battery",
+        Metric::Fps => "fps",
+        Metric::Frametime => "frametime",
+        Metric::EngineVersion => "engine_version",
+        Metric::GpuName => "gpu_name",
+        Metric::VulkanDriver => "vulkan_driver",
+        Metric::Arch => "arch",
+        Metric::Wine => "wine",
+        Metric::ExecName => "exec_name",
+        Metric::FrameTiming => "frame_timing",
+        Metric::FrameCount => "frame_count",
+        Metric::FpsLimit => "show_fps_limit",
+        Metric::Resolution => "resolution",
+        Metric::Gamemode => "gamemode",
+        Metric::VkBasalt => "vkbasalt",
+        Metric::Throttling => "throttling_status",
+        Metric::MediaPlayer => "media_player",
+        Metric::CustomTextCenter => "custom_text_center",
+        Metric::CustomText => "custom_text",
+    };
+    OPTIONS
+        .iter()
+        .position(|option| option.key == key)
+        .expect("every metric should have an option")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hud_compact_shrinks_the_estimate() {
+        let mut config = MangoHudConfig::new();
+        let (width, height) = config.estimated_size();
+
+        config.hud_compact = true;
+        let (compact_width, compact_height) = config.estimated_size();
+        assert!(compact_width < width, "{compact_width} < {width}");
+        assert!(compact_height < height, "{compact_height} < {height}");
+
+        config.table_columns = 1;
+        assert!(config.estimated_size().0 < compact_width);
+    }
+
+    #[test]
+    fn effective_background_applies_background_alpha() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("background_color", "102030").unwrap();
+        config.set_by_key("background_alpha", "0.5").unwrap();
+        assert_eq!(
+            config.effective_background(),
+            RGBA8::new(0x10, 0x20, 0x30, 128)
+        );
+
+        // Out of range values set directly are clamped
+        config.background_alpha = 2.0;
+        assert_eq!(config.effective_background().a, 255);
+    }
+
+    #[test]
+    fn no_small_font_is_a_floor() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("font_size", "20").unwrap();
+        assert_eq!(config.scaled_font_size(0.5), 10.0);
+        assert_eq!(config.scaled_font_size(2.0), 40.0);
+
+        config.set_by_key("no_small_font", "1").unwrap();
+        assert_eq!(config.scaled_font_size(0.5), 20.0);
+        assert_eq!(config.scaled_font_size(2.0), 40.0);
+    }
+
+    #[test]
+    fn disabled_metrics_are_left_out() {
+        let mut config = MangoHudConfig::new();
+        assert!(config.enabled_metrics().contains(&Metric::Frametime));
+
+        config.set_by_key("frametime", "0").unwrap();
+        let metrics = config.enabled_metrics();
+        assert!(!metrics.contains(&Metric::Frametime), "{metrics:?}");
+        assert!(metrics.contains(&Metric::Fps), "{metrics:?}");
+    }
+
+    #[test]
+    fn offset_and_size_round_trip() {
+        let mut config = MangoHudConfig::new();
+        assert_eq!(config.hud_offset(), (0.0, 0.0));
+
+        config.set_hud_offset(12.5, -4.0);
+        config.set_hud_size(300.0, 120.0);
+        assert_eq!(config.hud_offset(), (12.5, -4.0));
+        assert_eq!(config.hud_size(), (300.0, 120.0));
+        assert_eq!(config.get_by_key("offset_x").as_deref(), Some("12.5"));
+        assert_eq!(config.get_by_key("offset_y").as_deref(), Some("-4"));
+    }
+}
@@ -0,0 +1,377 @@
+//! Settings that are valid on their own, but likely don't do what the user intended,
+//! e.g. because they have no effect given the rest of the config.
+
+use std::fmt;
+
+use super::{HudPosition, MangoHudConfig};
+
+/// Labels longer than this overflow their column in the default layout.
+const MAX_LABEL_LEN: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    pub key: &'static str,
+    /// What's wrong with the setting.
+    pub message: String,
+    /// How to fix the setting.
+    pub suggestion: String,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {} ({})", self.key, self.message, self.suggestion)
+    }
+}
+
+impl MangoHudConfig {
+    /// Returns every setting that won't have an effect given the other settings,
+    /// or that is likely a mistake.
+    pub fn lint(&self) -> Vec<Lint> {
+        let defaults = Self::new();
+        let mut lints = Vec::new();
+        let mut push = |key, message: &str, suggestion: &str| {
+            lints.push(Lint {
+                key,
+                message: message.into(),
+                suggestion: suggestion.into(),
+            })
+        };
+
+        if !self.vram {
+            if self.gpu_mem_clock {
+                push(
+                    "gpu_mem_clock",
+                    "Has no effect unless `vram` is enabled",
+                    "Enable `vram`",
+                );
+            }
+            if self.gpu_mem_temp {
+                push(
+                    "gpu_mem_temp",
+                    "Has no effect unless `vram` is enabled",
+                    "Enable `vram`",
+                );
+            }
+        }
+
+        if !self.horizontal && self.horizontal_stretch != defaults.horizontal_stretch {
+            push(
+                "horizontal_stretch",
+                "Has no effect unless `horizontal` is enabled",
+                "Enable `horizontal` or remove `horizontal_stretch`",
+            );
+        }
+
+        if !self.text_outline && self.text_outline_thickness != defaults.text_outline_thickness {
+            push(
+                "text_outline_thickness",
+                "Has no effect unless `text_outline` is enabled",
+                "Enable `text_outline` or remove `text_outline_thickness`",
+            );
+        }
+
+        // The horizontal layout is a strip along the top or bottom edge of the screen,
+        // which is where every position except the middle ones puts it
+        if self.horizontal
+            && matches!(
+                self.position,
+                HudPosition::MiddleLeft | HudPosition::MiddleRight
+            )
+        {
+            push(
+                "position",
+                &format!(
+                    "The horizontal layout is meant for the top or bottom edge, at `{}` it ends up \
+                     in the middle of the screen",
+                    self.position
+                ),
+                "Use a `top-*` or `bottom-*` position",
+            );
+        }
+
+        if self.upload_log != defaults.upload_log && !self.permit_upload {
+            push(
+                "upload_log",
+                "Logs are only uploaded if `permit_upload` is enabled",
+                "Enable `permit_upload` or remove `upload_log`",
+            );
+        }
+
+        if !self.procmem {
+            if self.procmem_shared {
+                push(
+                    "procmem_shared",
+                    "Has no effect unless `procmem` is enabled",
+                    "Enable `procmem`",
+                );
+            }
+            if self.procmem_virt {
+                push(
+                    "procmem_virt",
+                    "Has no effect unless `procmem` is enabled",
+                    "Enable `procmem`",
+                );
+            }
+        }
+
+        if self.battery_icon && !self.battery {
+            push(
+                "battery_icon",
+                "The icon is only rendered if `battery` is enabled",
+                "Enable `battery`",
+            );
+        }
+        if self.gamepad_battery_icon && !self.gamepad_battery {
+            push(
+                "gamepad_battery_icon",
+                "The icon is only rendered if `gamepad_battery` is enabled",
+                "Enable `gamepad_battery`",
+            );
+        }
+
+        let filters: Vec<_> = [
+            ("bicubic", self.bicubic),
+            ("trilinear", self.trilinear),
+            ("retro", self.retro),
+        ]
+        .into_iter()
+        .filter_map(|(key, enabled)| enabled.then_some(key))
+        .collect();
+        if let [first, rest @ ..] = filters.as_slice() {
+            for key in rest {
+                push(
+                    key,
+                    &format!(
+                        "MangoHud applies only one texture filter, so it picks either `{first}` \
+                         or `{key}`"
+                    ),
+                    "Enable only one of `bicubic`, `trilinear` and `retro`",
+                );
+            }
+        }
+
+        if let Some(limit) = self.fps_limit.iter().find(|&&limit| limit > 1000) {
+            push(
+                "fps_limit",
+                &format!("A limit of {limit} fps is unusually high, likely a typo"),
+                "Use 0 for no limit",
+            );
+        }
+
+        if self.vsync.is_some() && self.gl_vsync.is_some() {
+            push(
+                "gl_vsync",
+                "`vsync` only applies to Vulkan and `gl_vsync` only to OpenGL, so only one of them \
+                 takes effect for a given game",
+                "Keep the one matching the game's graphics API",
+            );
+        }
+
+        if self.autostart_log && self.effective_output_folder().is_none() {
+            push(
+                "output_folder",
+                "No `output_folder` is set and the default one couldn't be resolved",
+                "Set `output_folder`",
+            );
+        }
+
+        if self.log_duration.subsec_nanos() != 0 {
+            push(
+                "log_duration",
+                &format!(
+                    "MangoHud only accepts whole seconds, {:?} is truncated to {}s",
+                    self.log_duration,
+                    self.log_duration_secs()
+                ),
+                "Use a whole number of seconds",
+            );
+        }
+
+        if !self.fcat {
+            if self.fcat_overlay_width != defaults.fcat_overlay_width {
+                push(
+                    "fcat_overlay_width",
+                    "Has no effect unless `fcat` is enabled",
+                    "Enable `fcat` or remove `fcat_overlay_width`",
+                );
+            }
+            if self.fcat_screen_edge != defaults.fcat_screen_edge {
+                push(
+                    "fcat_screen_edge",
+                    "Has no effect unless `fcat` is enabled",
+                    "Enable `fcat` or remove `fcat_screen_edge`",
+                );
+            }
+        }
+
+        if self.resolution && self.fcat {
+            push(
+                "resolution",
+                "`fcat` draws its own overlay for frame capture analysis, so the resolution text \
+                 ends up in the captures",
+                "Disable `resolution` while capturing with `fcat`",
+            );
+        }
+
+        for (key, label) in [("gpu_text", &self.gpu_text), ("cpu_text", &self.cpu_text)] {
+            if label.chars().count() > MAX_LABEL_LEN {
+                push(
+                    key,
+                    &format!(
+                        "Labels longer than {MAX_LABEL_LEN} characters overflow into the values \
+                         next to them"
+                    ),
+                    "Use a shorter label",
+                );
+            }
+            if label.contains('{') && label.contains('}') {
+                push(
+                    key,
+                    "MangoHud doesn't support placeholders in labels, it's shown as is",
+                    "Remove the placeholder",
+                );
+            }
+        }
+
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lints(config: &MangoHudConfig) -> Vec<&'static str> {
+        config.lint().into_iter().map(|lint| lint.key).collect()
+    }
+
+    #[test]
+    fn reports_every_no_op_setting() {
+        let mut config = MangoHudConfig::new();
+        config.gpu_mem_clock = true;
+        config.horizontal_stretch = false;
+
+        assert_eq!(lints(&config), ["gpu_mem_clock", "horizontal_stretch"]);
+        assert!(config.lint().iter().all(|lint| !lint.suggestion.is_empty()));
+    }
+
+    #[test]
+    fn lints_have_distinct_messages() {
+        let mut config = MangoHudConfig::new();
+        for (key, value) in [
+            ("gpu_mem_clock", "1"),
+            ("horizontal", "1"),
+            ("position", "middle-left"),
+            ("battery_icon", "1"),
+            ("bicubic", "1"),
+            ("trilinear", "1"),
+            ("fps_limit", "9999"),
+            ("vsync", "1"),
+            ("gl_vsync", "1"),
+            ("fcat", "1"),
+            ("resolution", "1"),
+            ("gpu_text", "{gpu_name}"),
+        ] {
+            config.set_by_key(key, value).unwrap();
+        }
+
+        // One option per lint, as e.g. `gpu_mem_clock` and `gpu_mem_temp` share a message
+        let lints = config.lint();
+        assert_eq!(lints.len(), 8, "{lints:#?}");
+        for (i, lint) in lints.iter().enumerate() {
+            assert!(
+                lints[..i].iter().all(|other| other.message != lint.message),
+                "{lints:#?}"
+            );
+        }
+    }
+
+    #[test]
+    fn text_outline_thickness_only_lints_when_changed() {
+        assert!(lints(&MangoHudConfig::new()).is_empty());
+
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("text_outline", "0").unwrap();
+        assert!(!lints(&config).contains(&"text_outline_thickness"));
+
+        config.set_by_key("text_outline_thickness", "2").unwrap();
+        assert!(lints(&config).contains(&"text_outline_thickness"));
+
+        config.set_by_key("text_outline", "1").unwrap();
+        assert!(!lints(&config).contains(&"text_outline_thickness"));
+    }
+
+    #[test]
+    fn unrealistic_fps_limit_still_parses() {
+        let config = crate::config::parse_str("fps_limit=60,9999\n").unwrap();
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("60,9999"));
+        assert_eq!(lints(&config), ["fps_limit"]);
+
+        let errors = config.validate().unwrap_err();
+        assert!(errors.iter().all(|error| error.is_warning()));
+    }
+
+    #[test]
+    fn vsync_and_gl_vsync_together() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("vsync", "1").unwrap();
+        assert!(lints(&config).is_empty());
+
+        config.set_by_key("gl_vsync", "0").unwrap();
+        assert_eq!(lints(&config), ["gl_vsync"]);
+    }
+
+    #[test]
+    fn long_labels() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("gpu_text", "RX 7900 XTX").unwrap();
+        assert!(lints(&config).is_empty());
+
+        config
+            .set_by_key("gpu_text", "AMD Radeon RX 7900 XTX Graphics")
+            .unwrap();
+        // 16 characters, but more bytes
+        config.set_by_key("cpu_text", "Ryzen 7 ✓✓✓✓✓✓✓✓").unwrap();
+        assert_eq!(lints(&config), ["gpu_text"]);
+    }
+
+    #[test]
+    fn resolution_with_fcat() {
+        let mut config = crate::config::parse_str("resolution=1\n").unwrap();
+        assert!(config.resolution);
+        assert_eq!(config.to_config_string().unwrap(), "resolution=1\n");
+        assert!(lints(&config).is_empty());
+
+        config.set_by_key("fcat", "1").unwrap();
+        assert_eq!(lints(&config), ["resolution"]);
+    }
+
+    #[test]
+    fn only_one_texture_filter() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("bicubic", "1").unwrap();
+        assert!(lints(&config).is_empty());
+
+        config.set_by_key("trilinear", "1").unwrap();
+        assert_eq!(lints(&config), ["trilinear"]);
+        assert!(config.validate().unwrap_err()[0].is_warning());
+    }
+
+    #[test]
+    fn horizontal_layout_in_the_middle() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("horizontal", "1").unwrap();
+        for position in ["top-center", "top-left", "bottom-right"] {
+            config.set_by_key("position", position).unwrap();
+            assert!(lints(&config).is_empty(), "{position}");
+        }
+
+        for position in ["middle-left", "middle-right"] {
+            config.set_by_key("position", position).unwrap();
+            assert_eq!(lints(&config), ["position"], "{position}");
+        }
+
+        config.set_by_key("horizontal", "0").unwrap();
+        assert!(lints(&config).is_empty());
+    }
+}
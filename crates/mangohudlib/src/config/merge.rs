@@ -0,0 +1,90 @@
+//! Layering configs on top of each other, e.g. per-game overrides on top of a global config.
+
+use std::mem;
+
+use eyre::Result;
+
+use super::{MangoHudConfig, SerializeOptions, SparseConfig};
+
+impl MangoHudConfig {
+    /// Applies the options `other` changes from [`MangoHudConfig::new`] on top of this config. \
+    /// Options `other` explicitly sets to their default can't be told apart from unset ones and
+    /// are left as is, merge the [`SparseConfig`] of a file with [`MangoHudConfig::merge_sparse`]
+    /// to have those overwrite this config too.
+    ///
+    /// Lists are replaced as a whole, e.g. `fps_limit`, since the order of its values is the order
+    /// `toggle_fps_limit` cycles through. Except for `blacklist`, which is appended to,
+    /// so an override can't accidentally show the hud in programs the base config hides it in.
+    pub fn merge(&mut self, other: &MangoHudConfig) -> Result<()> {
+        for (option, value) in other.changed_options(&SerializeOptions::default()) {
+            self.merge_entry(option.key, &value)?;
+        }
+        self.extra
+            .extend(other.extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Ok(())
+    }
+
+    /// [`MangoHudConfig::merge`], overwriting exactly the options `other`'s file sets.
+    pub fn merge_sparse(&mut self, other: &SparseConfig) -> Result<()> {
+        for (key, value) in other.iter() {
+            self.merge_entry(key, value)?;
+        }
+        self.extra
+            .extend(other.extra().iter().map(|(k, v)| (k.clone(), v.clone())));
+        Ok(())
+    }
+
+    fn merge_entry(&mut self, key: &str, value: &str) -> Result<()> {
+        if key != "blacklist" {
+            return self.set_by_key(key, value);
+        }
+
+        let base = self.blacklist.clone();
+        self.set_by_key(key, value)?;
+        let added = mem::replace(&mut self.blacklist, base);
+        for program in added {
+            if !self.blacklist.contains(&program) {
+                self.blacklist.push(program);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{parse_sparse_str, parse_str};
+
+    const BASE: &str = "fps_limit=30,60\nblacklist=steam\ngpu_temp=1\nalpha=0.5\n";
+
+    #[test]
+    fn merge_replaces_fps_limit_and_appends_blacklist() {
+        let mut config = parse_str(BASE).unwrap();
+        let game = parse_str("fps_limit=144\nblacklist=lutris,steam\n").unwrap();
+        config.merge(&game).unwrap();
+
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("144"));
+        assert_eq!(
+            config.get_by_key("blacklist").as_deref(),
+            Some("steam,lutris")
+        );
+        assert_eq!(config.get_by_key("gpu_temp").as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn merge_sparse_only_overwrites_what_the_file_sets() {
+        // `alpha=1` is the default, so only the sparse config knows the file sets it
+        let game = "alpha=1\n";
+
+        let mut config = parse_str(BASE).unwrap();
+        config.merge(&parse_str(game).unwrap()).unwrap();
+        assert_eq!(config.get_by_key("alpha").as_deref(), Some("0.5"));
+
+        let mut config = parse_str(BASE).unwrap();
+        config
+            .merge_sparse(&parse_sparse_str(game).unwrap())
+            .unwrap();
+        assert_eq!(config.get_by_key("alpha").as_deref(), Some("1"));
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("30,60"));
+    }
+}
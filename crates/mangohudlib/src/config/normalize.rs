@@ -0,0 +1,79 @@
+//! Canonicalization of configs, so equivalent configs serialize the same way.
+
+use std::path::{Path, PathBuf};
+
+use super::{default_output_folder, MangoHudConfig};
+
+impl MangoHudConfig {
+    /// Rewrites settings into a canonical form without changing what they do:
+    /// - Drops repeated `fps_limit` values. The order is kept, since the first value is the limit
+    ///   the game starts with and the rest is the order `toggle_fps_limit` cycles through.
+    /// - Drops repeated `blacklist` entries.
+    /// - Cleans up paths, e.g. `a/./b/` becomes `a/b`,
+    ///   and clears `output_folder` if it's the default one.
+    ///
+    /// Colors and keybinds need no normalization, they're always serialized in a canonical form.
+    ///
+    /// Normalizing an already normalized config doesn't change it.
+    pub fn normalize(&mut self) {
+        dedup_in_order(&mut self.fps_limit);
+        dedup_in_order(&mut self.blacklist);
+
+        for path in [
+            &mut self.font_file,
+            &mut self.font_file_text,
+            &mut self.output_folder,
+        ] {
+            *path = clean_path(path);
+        }
+        if default_output_folder().as_ref() == Some(&self.output_folder) {
+            self.output_folder = PathBuf::new();
+        }
+    }
+}
+
+/// Removes every repeated element, keeping the first occurrence.
+fn dedup_in_order<T: PartialEq>(list: &mut Vec<T>) {
+    let mut i = 0;
+    while i < list.len() {
+        if list[..i].contains(&list[i]) {
+            list.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// Drops `.` components, repeated and trailing separators without touching the filesystem.
+fn clean_path(path: &Path) -> PathBuf {
+    path.components().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::parse_str;
+
+    #[test]
+    fn normalize_is_idempotent() {
+        let mut config = parse_str(
+            "fps_limit=60,30,60,0\nblacklist=steam,lutris,steam\nfont_file=/usr/./share//fonts/a.ttf\n\
+             toggle_hud=Shift_L+Control_L+F12\ngpu_color=00FF00\n",
+        )
+        .unwrap();
+
+        config.normalize();
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("60,30,0"));
+        assert_eq!(
+            config.get_by_key("blacklist").as_deref(),
+            Some("steam,lutris")
+        );
+        assert_eq!(
+            config.get_by_key("font_file").as_deref(),
+            Some("/usr/share/fonts/a.ttf")
+        );
+
+        let normalized = config.clone();
+        config.normalize();
+        assert_eq!(config, normalized);
+    }
+}
@@ -0,0 +1,942 @@
+//! Registry of every option MangoHud's config understands, along with how each
+//! one is read from and written to its config representation.
+
+use std::{any::Any, fmt, ops::RangeInclusive, path::PathBuf, str::FromStr, time::Duration};
+
+use eyre::Result;
+use rgb::RGB8;
+
+use super::{constraint::*, *};
+
+/// Parsing and serialization of a single option value.
+pub(super) trait FieldParser: Sized {
+    fn parse_field(value: &str) -> Result<Self>;
+    /// `None` if the value is unset.
+    fn format_field(&self, options: &SerializeOptions) -> Option<String>;
+}
+
+pub struct OptionMeta {
+    pub key: &'static str,
+    /// Section of MangoHud's documentation the option is listed under, e.g. `Performance`.
+    pub group: &'static str,
+    /// Separator list values are joined with when serialized, `None` for non-list options. \
+    /// Parsing accepts both `,` and `+`, so any separator written here reads back.
+    pub separator: Option<&'static str>,
+    doc: &'static str,
+    set: fn(&mut MangoHudConfig, &str) -> Result<()>,
+    get: fn(&MangoHudConfig, &SerializeOptions) -> Option<String>,
+    is_bool: fn(&MangoHudConfig) -> bool,
+    constraint: fn() -> Constraint,
+}
+
+/// Generates an [`OptionMeta`] per field, grouped by section. \
+/// Doc comments become the option's documentation. \
+/// Fields use their type's [`FieldParser`] impl unless a `(parse, format)` pair is given. \
+/// List fields give their separator in brackets, e.g. `fps_limit [","]`, a custom `format`
+/// of a list field is passed the separator as well.
+macro_rules! options {
+    ($($group:literal {
+        $($(#[doc = $doc:literal])* $field:ident $([$sep:literal])? $(=> ($parse:path, $format:path))?,)*
+    })*) => {
+        /// Every option, grouped and ordered like the sections of MangoHud's example config,
+        /// `data/MangoHud.conf`. Configs are serialized in this order, so generated files
+        /// read like the upstream examples.
+        pub static OPTIONS: &[OptionMeta] = &[$($(
+            OptionMeta {
+                key: stringify!($field),
+                group: $group,
+                separator: options!(@separator $($sep)?),
+                doc: concat!($($doc),*),
+                set: |config, value| {
+                    config.$field = options!(@parse value $(, $parse)?)?;
+                    Ok(())
+                },
+                get: |config, options| {
+                    options!(@format &config.$field, options, [$($sep)?] $(, $format)?)
+                },
+                is_bool: |config| (&config.$field as &dyn Any).is::<bool>(),
+                constraint: || constraint_of(|config| &config.$field),
+            },
+        )*)*];
+    };
+    (@parse $value:ident) => { FieldParser::parse_field($value) };
+    (@parse $value:ident, $parse:path) => { $parse($value) };
+    (@separator) => { None };
+    (@separator $sep:literal) => { Some($sep) };
+    (@format $field:expr, $options:ident, []) => { FieldParser::format_field($field, $options) };
+    (@format $field:expr, $options:ident, [$sep:literal]) => {
+        Some(format_list(&$field[..], $sep, $options))
+    };
+    (@format $field:expr, $options:ident, [], $format:path) => { $format($field, $options) };
+    (@format $field:expr, $options:ident, [$sep:literal], $format:path) => {
+        $format($field, $sep, $options)
+    };
+}
+
+options! {
+    "Performance" {
+        /// Limit the fps, multiple limits can be toggled through with `toggle_fps_limit`. 0 means unlimited
+        fps_limit [","],
+        /// When to sleep to limit the fps, `late` gives lower latency while `early` gives smoother frametimes
+        fps_limit_method,
+        /// Vulkan vsync mode (0 = adaptive, 1 = off, 2 = mailbox, 3 = on)
+        vsync,
+        /// OpenGL vsync mode (0 = off, >=1 = wait for that many vblanks)
+        gl_vsync,
+        /// [-16 to 16] Mip-map LoD bias, negative values increase texture sharpness
+        picmip => (parse_picmip, FieldParser::format_field),
+        /// [0 to 16] Anisotropic filtering level
+        af => (parse_af, FieldParser::format_field),
+        /// Force bicubic filtering
+        bicubic,
+        /// Force trilinear filtering
+        trilinear,
+        /// Disable linear texture filtering
+        retro,
+    }
+
+    "Core Visual" {
+        /// Use the legacy layout, which also enables the default options
+        legacy_layout,
+        /// Use one of MangoHud's built-in presets (-1 = default, 0 = off, 1 = fps only, 2 = horizontal, 3 = extended, 4 = detailed)
+        preset,
+        /// Show the frametime graph as a histogram instead of a line graph
+        histogram,
+        /// Display custom text centered, useful for a header
+        custom_text_center,
+        /// Show the current system time
+        time,
+        /// `strftime` format string for `time`
+        time_format,
+        /// Show the MangoHud version
+        version,
+    }
+
+    "GPU Info" {
+        /// Show the GPU load
+        gpu_stats,
+        /// Show the GPU temperature
+        gpu_temp,
+        /// Show the GPU junction temperature
+        gpu_junction_temp,
+        /// Show the GPU core clock
+        gpu_core_clock,
+        /// Show the GPU memory temperature, requires `vram`
+        gpu_mem_temp,
+        /// Show the GPU memory clock, requires `vram`
+        gpu_mem_clock,
+        /// Show the GPU power draw
+        gpu_power,
+        /// Override the GPU label
+        gpu_text,
+        /// Switch between colors depending on the GPU load
+        gpu_load_change,
+        /// Medium and high GPU load values for `gpu_load_change`
+        gpu_load_value [","],
+        /// Colors for before medium, between medium and high, and after high GPU load
+        gpu_load_color [","],
+    }
+
+    "CPU Info" {
+        /// Show the CPU load
+        cpu_stats,
+        /// Show the CPU temperature
+        cpu_temp,
+        /// Show the CPU power draw
+        cpu_power,
+        /// Override the CPU label
+        cpu_text,
+        /// Show the CPU frequency
+        cpu_mhz,
+        /// Switch between colors depending on the CPU load
+        cpu_load_change,
+        /// Medium and high CPU load values for `cpu_load_change`
+        cpu_load_value [","],
+        /// Colors for before medium, between medium and high, and after high CPU load
+        cpu_load_color [","],
+        /// Show the load of each CPU core
+        core_load,
+        /// Switch between colors depending on the core load
+        core_load_change,
+    }
+
+    "App IO" {
+        /// Show the disk read speed of the app
+        io_read,
+        /// Show the disk write speed of the app
+        io_write,
+    }
+
+    "Storage Usage" {
+        /// Show the VRAM usage
+        vram,
+        /// Show the system RAM usage
+        ram,
+        /// Show the swap usage next to the RAM usage
+        swap,
+    }
+
+    "Per Proc Memory Usage" {
+        /// Show the memory used by the app
+        procmem,
+        /// Show the shared memory used by the app, requires `procmem`
+        procmem_shared,
+        /// Show the virtual memory used by the app, requires `procmem`
+        procmem_virt,
+    }
+
+    "Battery Info" {
+        /// Show the battery charge and power draw
+        battery,
+        /// Show the battery charge as an icon, requires `battery`
+        battery_icon,
+        /// Show the battery of wireless gamepads
+        gamepad_battery,
+        /// Show the gamepad battery as an icon, requires `gamepad_battery`
+        gamepad_battery_icon,
+    }
+
+    "FPS Info" {
+        /// Show the fps
+        fps,
+        /// Time between fps updates, in milliseconds
+        fps_sampling_period => (parse_millis, format_millis),
+        /// Switch between colors depending on the fps
+        fps_color_change,
+        /// Medium and high fps values for `fps_color_change`
+        fps_value [","],
+        /// Colors for before medium, between medium and high, and after high fps
+        fps_color [","],
+        /// Show the frametime next to the fps
+        frametime,
+        /// Show the frametime graph
+        frame_timing,
+        /// Show the frame count
+        frame_count,
+        /// Show the current fps limit
+        show_fps_limit,
+    }
+
+    "Misc Info" {
+        /// Show whether the GPU is throttling
+        throttling_status,
+        /// Show the OpenGL or Vulkan version used by the engine
+        engine_version,
+        /// Show the GPU name
+        gpu_name,
+        /// Show the Vulkan driver
+        vulkan_driver,
+        /// Show the Wine or Proton version
+        wine,
+        /// Show the executable name
+        exec_name,
+        /// Show whether the app is 32 or 64 bit
+        arch,
+        /// Show whether Feral GameMode is active
+        gamemode,
+        /// Show whether vkBasalt is active
+        vkbasalt,
+        /// Show the render resolution
+        resolution,
+        /// Display custom text
+        custom_text,
+        /// Display the output of a shell command
+        exec,
+    }
+
+    "Media Info" {
+        /// Show the media player metadata
+        media_player,
+        /// MPRIS name of the media player to show, e.g. `spotify`
+        media_player_name => (parse_media_player, FieldParser::format_field),
+        /// Format of the media player metadata, e.g. `{title};{artist};{album}`
+        media_player_format,
+    }
+
+    "Hud Font" {
+        /// Font size
+        font_size,
+        /// Scale of all fonts
+        font_scale,
+        /// Font size of text like media metadata
+        font_size_text,
+        /// Scale of the media player font
+        font_scale_media_player,
+        /// Use the primary font size for smaller text like units
+        no_small_font,
+        /// Path to a TTF or OTF font file
+        font_file,
+        /// Path to a font file for text like media metadata
+        font_file_text,
+        /// Extra glyph ranges to load from the font, e.g. `korean` or `0x4e00-0x9fff`
+        font_glyph_ranges [","],
+        /// Draw an outline around the text
+        text_outline,
+        /// Thickness of the text outline
+        text_outline_thickness,
+    }
+
+    "Hud Appearance" {
+        /// Position of the hud on the screen
+        position,
+        /// Radius of the hud's corners
+        round_corners,
+        /// Remove the margins around the hud
+        hud_no_margin,
+        /// Display a compact version of the hud
+        hud_compact,
+        /// Lay the hud out in a single row
+        horizontal,
+        /// Stretch the background to the screen width, requires `horizontal`
+        horizontal_stretch,
+        /// Hide the hud by default
+        no_display,
+        /// Horizontal offset of the hud from `position`
+        offset_x,
+        /// Vertical offset of the hud from `position`
+        offset_y,
+        /// Custom hud width
+        width,
+        /// Custom hud height
+        height,
+        /// Number of columns in the hud's table
+        table_columns,
+        /// Vertical padding of the table cells
+        cellpadding_y,
+        /// [0.0 to 1.0] Opacity of the background
+        background_alpha,
+        /// [0.0 to 1.0] Opacity of the text and graphs
+        alpha,
+    }
+
+    "FCAT Overlay" {
+        /// Enable the FCAT overlay
+        fcat,
+        /// Width of the FCAT overlay
+        fcat_overlay_width,
+        /// Screen edge to place the FCAT overlay on (0 = left, 1 = bottom, 2 = right, 3 = top)
+        fcat_screen_edge,
+    }
+
+    "Color" {
+        /// Color of the text
+        text_color,
+        /// Color of the GPU label
+        gpu_color,
+        /// Color of the CPU label
+        cpu_color,
+        /// Color of the VRAM label
+        vram_color,
+        /// Color of the RAM label
+        ram_color,
+        /// Color of the engine label
+        engine_color,
+        /// Color of the IO label
+        io_color,
+        /// Color of the frametime graph
+        frametime_color,
+        /// Color of the background
+        background_color,
+        /// Color of the media player text
+        media_player_color,
+        /// Color of the Wine label
+        wine_color,
+        /// Color of the battery label
+        battery_color,
+        /// Color of the text outline
+        text_outline_color,
+    }
+
+    "Other" {
+        /// PCI address of the GPU to show stats for, e.g. `0000:03:00.0`
+        pci_dev,
+        /// Names of programs the hud shouldn't be shown in
+        blacklist [","],
+        /// Name of the socket MangoHud can be controlled through
+        control,
+    }
+
+    "OpenGL Workarounds" {
+        /// (Re)bind the given framebuffer before drawing the hud in OpenGL
+        gl_bind_framebuffer,
+    }
+
+    "Keybinds" {
+        /// Keybind to toggle the hud
+        toggle_hud,
+        /// Keybind to cycle through hud positions
+        toggle_hud_position,
+        /// Keybind to cycle through `fps_limit` values
+        toggle_fps_limit,
+        /// Keybind to toggle logging
+        toggle_logging,
+        /// Keybind to reload the config
+        reload_cfg,
+        /// Keybind to upload the last log, requires `permit_upload`
+        upload_log,
+    }
+
+    "Logging" {
+        /// Start logging automatically
+        autostart_log,
+        /// Stop logging after the given number of seconds
+        log_duration => (parse_secs, format_secs),
+        /// Time between log entries, in milliseconds
+        log_interval => (parse_millis, format_millis),
+        /// Folder logs are written to
+        output_folder,
+        /// Allow uploading logs to Flightlessmango.com
+        permit_upload,
+        /// Percentiles to show in the benchmark results, e.g. `97+AVG`
+        benchmark_percentiles ["+"],
+    }
+}
+
+impl OptionMeta {
+    /// Short description of the option, taken from MangoHud's documentation.
+    pub fn doc(&self) -> Option<&'static str> {
+        Some(self.doc.trim()).filter(|doc| !doc.is_empty())
+    }
+
+    /// Values the option accepts, an input outside of them fails to parse or validate.
+    pub fn constraint(&self) -> Constraint {
+        match self.key {
+            "picmip" => int_range(PICMIP_RANGE),
+            "af" => int_range(AF_RANGE),
+            "alpha" | "background_alpha" => Constraint::FloatRange(ALPHA_RANGE),
+            "text_outline_thickness" => Constraint::FloatRange(TEXT_OUTLINE_THICKNESS_RANGE),
+            "table_columns" => int_range(TABLE_COLUMNS_RANGE),
+            "fcat_overlay_width" => int_range(FCAT_OVERLAY_WIDTH_RANGE),
+            _ => (self.constraint)(),
+        }
+    }
+}
+
+/// Synonyms used by other tools, mapped to the MangoHud key they stand for.
+const ALIASES: &[(&str, &str)] = &[
+    ("fps_cap", "fps_limit"),
+    ("hud_position", "position"),
+    ("transparency", "background_alpha"),
+];
+
+/// Resolves [`ALIASES`] to the MangoHud key, warning about the alias being used.
+pub(super) fn canonical_key<'a>(key: &'a str, warnings: &mut Vec<ParseWarning>) -> &'a str {
+    let key = key.trim();
+    match ALIASES.iter().find(|&&(alias, _)| alias == key) {
+        Some(&(alias, canonical)) => {
+            warnings.push(ParseWarning::new(
+                alias,
+                format!("Not a MangoHud option, treated as `{canonical}`"),
+            ));
+            canonical
+        }
+        None => key,
+    }
+}
+
+/// Whether `key` is one of [`OPTIONS`] or [`ALIASES`].
+pub(super) fn is_known_key(key: &str) -> bool {
+    OPTIONS.iter().any(|option| option.key == key) || ALIASES.iter().any(|&(alias, _)| alias == key)
+}
+
+/// Documentation of the option with the given key, see [`OptionMeta::doc`].
+pub fn doc_for(key: &str) -> Option<&'static str> {
+    OPTIONS
+        .iter()
+        .find(|option| option.key == key)
+        .and_then(OptionMeta::doc)
+}
+
+impl MangoHudConfig {
+    /// Sets a single option from its MangoHud config representation, e.g. `("alpha", "0.7")`.
+    pub fn set_by_key(&mut self, key: &str, value: &str) -> Result<()> {
+        let key = key.trim();
+        let option = OPTIONS
+            .iter()
+            .find(|option| option.key == key)
+            .ok_or_else(|| eyre::eyre!("Unknown config option: {key}"))?;
+        (option.set)(self, value.trim())
+    }
+
+    /// Returns a copy of the config with a single option changed, see [`MangoHudConfig::set_by_key`].
+    pub fn with_override(&self, key: &str, value: &str) -> Result<MangoHudConfig> {
+        let mut config = self.clone();
+        config.set_by_key(key, value)?;
+        Ok(config)
+    }
+
+    /// Sets every `(key, value)` pair, e.g. from a launcher's `--set key=value` arguments. \
+    /// An empty value enables a bool option, for bare flags like `--set no_display`,
+    /// and is an error for any other option.
+    ///
+    /// Every valid pair is applied even if others fail, the error lists every pair that failed.
+    pub fn apply_overrides(&mut self, pairs: &[(String, String)]) -> Result<()> {
+        let mut errors = Vec::new();
+
+        for (key, value) in pairs {
+            let is_bool = OPTIONS
+                .iter()
+                .any(|option| option.key == key.trim() && (option.is_bool)(self));
+            let value = match value.trim() {
+                "" if is_bool => "1",
+                "" => {
+                    errors.push(format!(
+                        "`{key}`: Missing a value, only bool options can be set as a bare flag"
+                    ));
+                    continue;
+                }
+                _ => value,
+            };
+
+            if let Err(e) = self.set_by_key(key, value) {
+                errors.push(format!("`{key}={value}`: {e:#}"));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            eyre::bail!(
+                "Failed to apply {} override(s):\n{}",
+                errors.len(),
+                errors.join("\n")
+            )
+        }
+    }
+
+    /// Returns the MangoHud config representation of a single option. \
+    /// `None` if the option is unknown or unset.
+    pub fn get_by_key(&self, key: &str) -> Option<String> {
+        let key = key.trim();
+        OPTIONS
+            .iter()
+            .find(|option| option.key == key)
+            .and_then(|option| (option.get)(self, &SerializeOptions::default()))
+    }
+}
+
+impl MangoHudConfig {
+    /// Options whose value differs from [`MangoHudConfig::new`], along with that value. \
+    /// Includes every bool if [`SerializeOptions::explicit_bools`] is set,
+    /// and every set option if [`SerializeOptions::all_options`] is.
+    pub(super) fn changed_options<'a>(
+        &'a self,
+        options: &'a SerializeOptions,
+    ) -> impl Iterator<Item = (&'static OptionMeta, String)> + 'a {
+        let defaults = Self::new();
+        OPTIONS.iter().filter_map(move |option| {
+            let value = (option.get)(self, options)?;
+            let explicit =
+                options.all_options || (options.explicit_bools && (option.is_bool)(self));
+            (explicit || Some(&value) != (option.get)(&defaults, options).as_ref())
+                .then_some((option, value))
+        })
+    }
+
+    /// Lists the options that differ from [`MangoHudConfig::new`] grouped by section,
+    /// in a stable order. Empty for the defaults.
+    pub fn debug_table(&self) -> String {
+        let mut out = String::new();
+        let mut group = None;
+
+        for (option, value) in self.changed_options(&SerializeOptions::default()) {
+            if group != Some(option.group) {
+                group = Some(option.group);
+                out.push_str(&format!("[{}]\n", option.group));
+            }
+            out.push_str(&format!("  {} = {value:?}\n", option.key));
+        }
+
+        out
+    }
+}
+
+/// Implements [`FieldParser`] through the type's [`FromStr`] and [`ToString`] impls.
+macro_rules! impl_field_parser {
+    ($($ty:ty),*) => {$(
+        impl FieldParser for $ty {
+            fn parse_field(value: &str) -> Result<Self> {
+                value.parse::<Self>().map_err(|e| eyre::eyre!(e))
+            }
+
+            fn format_field(&self, _: &SerializeOptions) -> Option<String> {
+                Some(self.to_string())
+            }
+        }
+    )*};
+}
+
+impl_field_parser!(
+    u8,
+    u16,
+    i8,
+    String,
+    FpsLimitMethod,
+    VSync,
+    HudPreset,
+    HudPosition,
+    FcatOverlayEdge,
+    GlyphRange,
+    Keybind,
+    MediaPlayer
+);
+
+/// Bools are serialized as 0/1. \
+/// `true`/`false`, `yes`/`no` and `on`/`off` are accepted too, for configs written by other tools.
+impl FieldParser for bool {
+    fn parse_field(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "0" | "false" | "no" | "off" => Ok(false),
+            "1" | "true" | "yes" | "on" => Ok(true),
+            _ => eyre::bail!("Expected 0 or 1, got {value:?}"),
+        }
+    }
+
+    fn format_field(&self, _: &SerializeOptions) -> Option<String> {
+        Some(if *self { "1" } else { "0" }.into())
+    }
+}
+
+/// Floats are serialized in the shortest form that parses back to the same value, e.g. `-0.085`. \
+/// Widening to `f64` before formatting would instead give `-0.08500000089406967`.
+impl FieldParser for f32 {
+    fn parse_field(value: &str) -> Result<Self> {
+        let n = value.parse::<f32>().map_err(|e| eyre::eyre!(e))?;
+        if !n.is_finite() {
+            eyre::bail!("Expected a finite number, got {value:?}");
+        }
+        Ok(n)
+    }
+
+    fn format_field(&self, _: &SerializeOptions) -> Option<String> {
+        // Avoids emitting `-0`
+        let n = if *self == 0.0 { 0.0 } else { *self };
+        Some(n.to_string())
+    }
+}
+
+/// Colors are serialized as lowercase hex without the `#`, e.g. `ffffff`,
+/// or as their name if [`SerializeOptions::color_names`] is set.
+impl FieldParser for RGB8 {
+    fn parse_field(value: &str) -> Result<Self> {
+        if let Some(color) = color_from_name(value) {
+            return Ok(color);
+        }
+
+        // MangoHud reads colors as a plain number and only looks at the low 24 bits,
+        // so `RRGGBBAA` would silently show up as a different color
+        let digits = value.strip_prefix('#').unwrap_or(value);
+        if digits.len() == 8 && digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            eyre::bail!(
+                "Expected a 6 digit hex color, got {value:?}. MangoHud doesn't support colors with \
+                 an alpha channel, transparency is set through `alpha` and `background_alpha`"
+            );
+        }
+
+        color_from_hex(value)
+    }
+
+    fn format_field(&self, options: &SerializeOptions) -> Option<String> {
+        let name = options.color_names.then(|| name_for_color(*self)).flatten();
+        Some(name.map_or_else(|| color_to_hex(*self), Into::into))
+    }
+}
+
+impl FieldParser for PathBuf {
+    fn parse_field(value: &str) -> Result<Self> {
+        Ok(value.into())
+    }
+
+    fn format_field(&self, _: &SerializeOptions) -> Option<String> {
+        Some(self.display().to_string())
+    }
+}
+
+impl<T: FieldParser> FieldParser for Option<T> {
+    fn parse_field(value: &str) -> Result<Self> {
+        T::parse_field(value).map(Some)
+    }
+
+    fn format_field(&self, options: &SerializeOptions) -> Option<String> {
+        self.as_ref().and_then(|value| value.format_field(options))
+    }
+}
+
+impl<T: FieldParser> FieldParser for Vec<T> {
+    fn parse_field(value: &str) -> Result<Self> {
+        parse_list(value)
+    }
+
+    fn format_field(&self, options: &SerializeOptions) -> Option<String> {
+        Some(format_list(self, ",", options))
+    }
+}
+
+impl<T: FieldParser, const N: usize> FieldParser for [T; N] {
+    fn parse_field(value: &str) -> Result<Self> {
+        parse_list(value)?
+            .try_into()
+            .map_err(|list: Vec<T>| eyre::eyre!("Expected {N} values, got {}", list.len()))
+    }
+
+    fn format_field(&self, options: &SerializeOptions) -> Option<String> {
+        Some(format_list(self, ",", options))
+    }
+}
+
+fn parse_list<T: FieldParser>(value: &str) -> Result<Vec<T>> {
+    if value.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    value
+        .split(&[',', '+'][..])
+        .map(|s| T::parse_field(s.trim()))
+        .collect::<Result<_>>()
+}
+
+pub(super) fn format_list<T: FieldParser>(
+    list: &[T],
+    separator: &str,
+    options: &SerializeOptions,
+) -> String {
+    list.iter()
+        .filter_map(|value| value.format_field(options))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn parse_millis(value: &str) -> Result<Duration> {
+    u64::from_str(value)
+        .map(Duration::from_millis)
+        .map_err(|e| eyre::eyre!(e))
+}
+
+fn format_millis(duration: &Duration, _: &SerializeOptions) -> Option<String> {
+    Some(duration.as_millis().to_string())
+}
+
+fn parse_secs(value: &str) -> Result<Duration> {
+    u64::from_str(value)
+        .map(Duration::from_secs)
+        .map_err(|e| eyre::eyre!(e))
+}
+
+/// Sub-second precision is truncated.
+fn format_secs(duration: &Duration, _: &SerializeOptions) -> Option<String> {
+    Some(duration.as_secs().to_string())
+}
+
+/// An empty name is MangoHud's way of showing whichever player is playing.
+fn parse_media_player(value: &str) -> Result<Option<MediaPlayer>> {
+    if value.trim().is_empty() {
+        return Ok(None);
+    }
+    MediaPlayer::parse_field(value).map(Some)
+}
+
+fn parse_picmip(value: &str) -> Result<Option<i8>> {
+    parse_bounded("picmip", value, PICMIP_RANGE).map(Some)
+}
+
+fn parse_af(value: &str) -> Result<Option<u8>> {
+    parse_bounded("af", value, AF_RANGE).map(Some)
+}
+
+/// Parses an integer, reporting values outside of `range` as [`ValidationErrorKind::OutOfRange`]
+/// rather than as an overflow of `T`.
+fn parse_bounded<T>(key: &'static str, value: &str, range: RangeInclusive<i64>) -> Result<T>
+where
+    T: TryFrom<i64>,
+    T::Error: fmt::Debug,
+{
+    let n = i64::from_str(value).map_err(|e| eyre::eyre!(e))?;
+    if !range.contains(&n) {
+        return Err(ValidationError::out_of_range(key, n, &range).into());
+    }
+    Ok(T::try_from(n).expect("range should fit in the target type"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_get_by_key() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("alpha", "0.7").unwrap();
+        assert_eq!(config.get_by_key("alpha").as_deref(), Some("0.7"));
+
+        config.set_by_key(" fps_limit ", " 30,60 ").unwrap();
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("30,60"));
+
+        assert!(config.set_by_key("no_such_option", "1").is_err());
+        assert_eq!(config.get_by_key("no_such_option"), None);
+    }
+
+    #[test]
+    fn picmip_and_af_out_of_range() {
+        let mut config = MangoHudConfig::new();
+        for (key, value) in [
+            ("picmip", "-20"),
+            ("picmip", "17"),
+            ("af", "-1"),
+            ("af", "20"),
+        ] {
+            let error = config.set_by_key(key, value).unwrap_err();
+            let error = error.downcast_ref::<ValidationError>().unwrap();
+            assert_eq!(error.key, key);
+            assert!(matches!(error.kind, ValidationErrorKind::OutOfRange { .. }));
+        }
+
+        config.set_by_key("picmip", "-16").unwrap();
+        config.set_by_key("af", "16").unwrap();
+        assert_eq!(config.get_by_key("picmip").as_deref(), Some("-16"));
+        assert_eq!(config.get_by_key("af").as_deref(), Some("16"));
+    }
+
+    #[test]
+    fn bool_words() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("gpu_stats", "off").unwrap();
+        assert_eq!(config.get_by_key("gpu_stats").as_deref(), Some("0"));
+        config.set_by_key("gpu_stats", "True").unwrap();
+        assert_eq!(config.get_by_key("gpu_stats").as_deref(), Some("1"));
+
+        assert!(config.set_by_key("gpu_stats", "2").is_err());
+        assert_eq!(config.get_by_key("gpu_stats").as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn apply_overrides_applies_the_valid_ones() {
+        let mut config = MangoHudConfig::new();
+        let pairs = [
+            ("fps_limit".to_string(), "60".to_string()),
+            ("alpha".to_string(), "opaque".to_string()),
+            ("no_display".to_string(), String::new()),
+        ];
+
+        let error = config.apply_overrides(&pairs).unwrap_err().to_string();
+        assert!(error.contains("1 override(s)"), "{error}");
+        assert!(error.contains("`alpha=opaque`"), "{error}");
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("60"));
+        assert_eq!(config.get_by_key("no_display").as_deref(), Some("1"));
+        assert_eq!(config.get_by_key("alpha").as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn apply_overrides_empty_value_is_only_a_flag_for_bools() {
+        let mut config = MangoHudConfig::new();
+        let pairs = [
+            ("fps_limit".to_string(), "60".to_string()),
+            ("fps_limit".to_string(), String::new()),
+        ];
+
+        let error = config.apply_overrides(&pairs).unwrap_err().to_string();
+        assert!(error.contains("`fps_limit`: Missing a value"), "{error}");
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("60"));
+    }
+
+    #[test]
+    fn doc_for_picmip() {
+        let doc = doc_for("picmip").unwrap();
+        assert!(doc.contains("-16 to 16"), "{doc}");
+        assert_eq!(doc_for("no_such_option"), None);
+    }
+
+    #[test]
+    fn floats_are_written_in_their_shortest_form() {
+        let config = MangoHudConfig::new();
+        assert_eq!(
+            config.get_by_key("cellpadding_y").as_deref(),
+            Some("-0.085")
+        );
+
+        let all = config
+            .to_config_string_with(&SerializeOptions {
+                all_options: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(all.contains("\ncellpadding_y=-0.085\n"), "{all}");
+    }
+
+    #[test]
+    fn with_override_leaves_the_original_unchanged() {
+        let config = MangoHudConfig::new();
+        let limited = config.with_override("fps_limit", "60").unwrap();
+        assert_eq!(limited.get_by_key("fps_limit").as_deref(), Some("60"));
+        assert_eq!(config, MangoHudConfig::new());
+
+        assert!(config.with_override("alpha", "opaque").is_err());
+    }
+
+    #[test]
+    fn debug_table_lists_changed_options_by_group() {
+        assert_eq!(MangoHudConfig::new().debug_table(), "");
+
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("gpu_temp", "1").unwrap();
+        config.set_by_key("fps_limit", "60").unwrap();
+        assert_eq!(
+            config.debug_table(),
+            "[Performance]\n  fps_limit = \"60\"\n[GPU Info]\n  gpu_temp = \"1\"\n"
+        );
+    }
+
+    #[test]
+    fn aliases_set_the_canonical_key_and_warn() {
+        let (config, warnings) = parse_str_with_warnings("fps_cap=60\n").unwrap();
+        assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("60"));
+        assert!(config.extra().is_empty());
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].key, "fps_cap");
+        assert!(warnings[0].message.contains("`fps_limit`"), "{warnings:?}");
+        assert!(!warnings[0].is_error);
+    }
+
+    #[test]
+    fn colors_with_alpha_are_rejected() {
+        let mut config = MangoHudConfig::new();
+        for value in ["ff000080", "#FF000080"] {
+            let error = config
+                .set_by_key("gpu_color", value)
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("background_alpha"), "{error}");
+        }
+        assert_eq!(config, MangoHudConfig::new());
+    }
+
+    #[test]
+    fn lists_are_written_with_their_separator() {
+        let separator = |key| {
+            OPTIONS
+                .iter()
+                .find(|option| option.key == key)
+                .unwrap()
+                .separator
+        };
+        assert_eq!(separator("benchmark_percentiles"), Some("+"));
+        assert_eq!(separator("gpu_load_color"), Some(","));
+        assert_eq!(separator("gpu_temp"), None);
+
+        // Either separator is read
+        let mut config = MangoHudConfig::new();
+        config
+            .set_by_key("benchmark_percentiles", "99,AVG")
+            .unwrap();
+        config
+            .set_by_key("gpu_load_color", "ff0000+00ff00+0000ff")
+            .unwrap();
+        assert_eq!(
+            config.get_by_key("benchmark_percentiles").as_deref(),
+            Some("99+AVG")
+        );
+        assert_eq!(
+            config.get_by_key("gpu_load_color").as_deref(),
+            Some("ff0000,00ff00,0000ff")
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! MangoHud's built-in hud presets. \
+//! https://github.com/flightlessmango/MangoHud/blob/master/data/presets.conf
+
+use super::{HudPreset, MangoHudConfig, OPTIONS};
+
+impl MangoHudConfig {
+    /// Sets `preset` and enables/disables the options that preset implies. \
+    /// This approximates MangoHud's built-in presets, options a preset doesn't touch are kept.
+    pub fn apply_preset(&mut self, preset: HudPreset) {
+        self.preset = preset;
+
+        match preset {
+            HudPreset::Default => {}
+            HudPreset::Off => self.no_display = true,
+            HudPreset::FpsOnly => {
+                self.legacy_layout = false;
+                self.cpu_stats = false;
+                self.gpu_stats = false;
+                self.fps = true;
+                self.frametime = false;
+                self.frame_timing = false;
+            }
+            HudPreset::Horizontal => {
+                self.legacy_layout = false;
+                self.horizontal = true;
+                self.hud_no_margin = true;
+                self.gpu_stats = true;
+                self.cpu_stats = true;
+                self.ram = true;
+                self.vram = true;
+                self.fps = true;
+                self.frametime = false;
+                self.frame_timing = true;
+                self.battery = true;
+                self.table_columns = 14;
+            }
+            HudPreset::Extended => {
+                self.legacy_layout = false;
+                self.gpu_stats = true;
+                self.gpu_temp = true;
+                self.gpu_core_clock = true;
+                self.gpu_mem_clock = true;
+                self.gpu_power = true;
+                self.vram = true;
+                self.cpu_stats = true;
+                self.cpu_temp = true;
+                self.cpu_power = true;
+                self.cpu_mhz = true;
+                self.ram = true;
+                self.fps = true;
+                self.frametime = true;
+                self.frame_timing = true;
+                self.arch = true;
+                self.wine = true;
+                self.gamemode = true;
+                self.vkbasalt = true;
+                self.show_fps_limit = true;
+                self.resolution = true;
+            }
+            HudPreset::Detailed => {
+                self.apply_preset(HudPreset::Extended);
+                self.preset = preset;
+                self.gpu_junction_temp = true;
+                self.gpu_mem_temp = true;
+                self.core_load = true;
+                self.io_read = true;
+                self.io_write = true;
+                self.swap = true;
+                self.procmem = true;
+                self.procmem_shared = true;
+                self.procmem_virt = true;
+                self.battery = true;
+                self.gamepad_battery = true;
+                self.throttling_status = true;
+                self.engine_version = true;
+                self.gpu_name = true;
+                self.vulkan_driver = true;
+                self.exec_name = true;
+                self.frame_count = true;
+                self.time = true;
+                self.version = true;
+            }
+        }
+    }
+
+    /// Options where the config differs from [`MangoHudConfig::new`] with `preset` applied,
+    /// along with this config's value for them.
+    pub fn deltas_from_preset(&self, preset: HudPreset) -> Vec<(&'static str, String)> {
+        let mut baseline = Self::new();
+        baseline.apply_preset(preset);
+
+        OPTIONS
+            .iter()
+            .filter_map(|option| {
+                let value = self.get_by_key(option.key);
+                (value != baseline.get_by_key(option.key))
+                    .then(|| (option.key, value.unwrap_or_default()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deltas_from_preset() {
+        let config = MangoHudConfig::new();
+        assert!(config.deltas_from_preset(config.preset).is_empty());
+
+        let mut config = MangoHudConfig::new();
+        config.apply_preset(HudPreset::Detailed);
+        config.set_by_key("fps_limit", "60").unwrap();
+        config.set_by_key("time", "0").unwrap();
+        assert_eq!(
+            config.deltas_from_preset(HudPreset::Detailed),
+            [("fps_limit", "60".to_owned()), ("time", "0".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Named configs stored as `<name>.conf` files in a directory.
+
+use std::path::Path;
+
+use eyre::{Result, WrapErr};
+
+use super::{parse, MangoHudConfig, SerializeOptions};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    /// File name of the profile without the `.conf` extension.
+    pub name: String,
+    pub config: MangoHudConfig,
+}
+
+/// Writes the profile to `<dir>/<name>.conf`, creating `dir` if needed.
+pub fn save_profile<P: AsRef<Path>>(dir: P, profile: &Profile) -> Result<()> {
+    let dir = dir.as_ref();
+    let name = &profile.name;
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        eyre::bail!("Invalid profile name {name:?}, it's used as a file name");
+    }
+
+    let content = profile
+        .config
+        .to_config_string_with(&SerializeOptions::default())
+        .wrap_err_with(|| format!("Failed to serialize profile {name:?}"))?;
+    fs_err::create_dir_all(dir)?;
+    fs_err::write(dir.join(format!("{name}.conf")), content)?;
+
+    Ok(())
+}
+
+/// Reads every `*.conf` file in `dir` as a profile, sorted by name.
+pub fn load_profiles<P: AsRef<Path>>(dir: P) -> Result<Vec<Profile>> {
+    let mut profiles = Vec::new();
+
+    for entry in fs_err::read_dir(dir.as_ref())? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension() != Some("conf".as_ref()) {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        profiles.push(Profile {
+            name: name.into(),
+            config: parse(&path)?,
+        });
+    }
+
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_profiles_names_them_after_their_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs_err::write(dir.path().join("work.conf"), "fps_limit=60\n").unwrap();
+        fs_err::write(dir.path().join("gaming.conf"), "fps_limit=144\n").unwrap();
+        fs_err::write(dir.path().join("notes.txt"), "not a profile").unwrap();
+
+        let profiles = load_profiles(dir.path()).unwrap();
+        let names: Vec<_> = profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect();
+        assert_eq!(names, ["gaming", "work"]);
+        assert_eq!(
+            profiles[0].config.get_by_key("fps_limit").as_deref(),
+            Some("144")
+        );
+    }
+
+    #[test]
+    fn saved_profiles_load_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("gpu_temp", "1").unwrap();
+        let profile = Profile {
+            name: "benchmark".into(),
+            config,
+        };
+
+        save_profile(dir.path().join("profiles"), &profile).unwrap();
+        assert_eq!(
+            load_profiles(dir.path().join("profiles")).unwrap(),
+            [profile]
+        );
+
+        let invalid = Profile {
+            name: "../escape".into(),
+            config: MangoHudConfig::new(),
+        };
+        assert!(save_profile(dir.path(), &invalid).is_err());
+    }
+}
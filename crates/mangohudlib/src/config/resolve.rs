@@ -0,0 +1,40 @@
+//! The config a game ends up with, combining the config file and `MANGOHUD_CONFIG` like MangoHud.
+
+use std::env;
+
+use eyre::{Result, WrapErr};
+
+use super::{locate_config, MangoHudConfig};
+
+/// Entry of `MANGOHUD_CONFIG` telling MangoHud to still read the config file.
+pub(super) const READ_CFG: &str = "read_cfg";
+
+/// Returns the effective config a game would see, the way MangoHud builds it:
+/// - Without `MANGOHUD_CONFIG`, the first of [`config_candidates`](super::config_candidates)
+///   that exists is applied on top of the built-in defaults. Only that one file is read.
+/// - With `MANGOHUD_CONFIG` set, only its options are applied on top of the defaults. \
+///   If it contains `read_cfg`, the config file is read first, with `MANGOHUD_CONFIG` taking
+///   precedence over it.
+pub fn resolve(app: Option<&str>) -> Result<MangoHudConfig> {
+    let mut config = MangoHudConfig::new();
+    let env_config = env::var("MANGOHUD_CONFIG").ok();
+
+    let read_cfg = env_config
+        .as_deref()
+        .is_none_or(|value| value.split(',').any(|entry| entry.trim() == READ_CFG));
+    if read_cfg {
+        if let Some(file) = locate_config(app) {
+            config
+                .apply_str(&fs_err::read_to_string(&file)?, &mut Vec::new())
+                .wrap_err_with(|| format!("Failed to parse {}", file.display()))?;
+        }
+    }
+
+    if let Some(value) = env_config {
+        config
+            .apply_env(&value, &mut Vec::new())
+            .wrap_err("Failed to parse MANGOHUD_CONFIG")?;
+    }
+
+    Ok(config)
+}
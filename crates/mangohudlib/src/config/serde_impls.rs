@@ -0,0 +1,123 @@
+//! `serde` representations for the types MangoHud's config writes in its own syntax,
+//! picked to read well as JSON rather than to match the config format.
+
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use rgb::RGB8;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{GlyphRange, Keybind, MediaPlayer};
+use crate::color::{color_from_hex, color_to_hex};
+
+/// Colors as hex strings, e.g. `"ff0000"`.
+pub(super) mod hex_color {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(color: &RGB8, serializer: S) -> Result<S::Ok, S::Error> {
+        color_to_hex(*color).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<RGB8, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        color_from_hex(&value).map_err(de::Error::custom)
+    }
+}
+
+/// [`hex_color`] for the load colors, e.g. `["00ff00", "fdfd09", "b22222"]`.
+pub(super) mod hex_colors {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(colors: &[RGB8; 3], serializer: S) -> Result<S::Ok, S::Error> {
+        colors.map(color_to_hex).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[RGB8; 3], D::Error> {
+        let [a, b, c] = <[String; 3]>::deserialize(deserializer)?;
+        Ok([
+            color_from_hex(&a).map_err(de::Error::custom)?,
+            color_from_hex(&b).map_err(de::Error::custom)?,
+            color_from_hex(&c).map_err(de::Error::custom)?,
+        ])
+    }
+}
+
+/// Durations as a number of milliseconds, whatever unit the config writes them in.
+pub(super) mod millis {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        u64::try_from(duration.as_millis())
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+/// Serializes types with a syntax of their own as a string in that syntax, e.g. `"Shift_R+F12"`.
+macro_rules! impl_serde_via_str {
+    ($($ty:ty),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                from_str(&String::deserialize(deserializer)?)
+            }
+        }
+    )*};
+}
+
+impl_serde_via_str!(Keybind, GlyphRange, MediaPlayer);
+
+fn from_str<T, E>(value: &str) -> Result<T, E>
+where
+    T: FromStr,
+    T::Err: Display,
+    E: de::Error,
+{
+    value.parse().map_err(E::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::config::{parse_str, MangoHudConfig};
+
+    #[test]
+    fn json_is_human_friendly_and_round_trips() {
+        let config = parse_str(
+            "vsync=2\nposition=bottom-right\ntext_color=FF8000\nfps_sampling_period=250\n\
+             toggle_hud=Shift_L+F10\nmedia_player_name=spotify\n",
+        )
+        .unwrap();
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["vsync"], json!("mailbox"));
+        assert_eq!(value["position"], json!("bottom_right"));
+        assert_eq!(value["text_color"], json!("ff8000"));
+        assert_eq!(value["fps_sampling_period"], json!(250));
+        assert_eq!(value["toggle_hud"], json!("Shift_L+F10"));
+        assert_eq!(value["media_player_name"], json!("spotify"));
+
+        let back: MangoHudConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(back, config);
+    }
+
+    #[test]
+    fn missing_fields_are_the_defaults() {
+        let config: MangoHudConfig = serde_json::from_value(json!({ "gpu_temp": true })).unwrap();
+        let mut expected = MangoHudConfig::new();
+        expected.set_by_key("gpu_temp", "1").unwrap();
+        assert_eq!(config, expected);
+
+        let invalid = json!({ "text_color": "not a color" });
+        assert!(serde_json::from_value::<MangoHudConfig>(invalid).is_err());
+    }
+}
@@ -0,0 +1,332 @@
+//! Writing configs back to MangoHud's config format.
+
+use std::{fmt, path::Path};
+
+use eyre::Result;
+
+use super::MangoHudConfig;
+
+/// `exec` commands longer than this are wrapped onto continuation lines with
+/// [`SerializeOptions::wrap_exec`].
+const EXEC_WRAP_WIDTH: usize = 80;
+
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOptions {
+    /// Write colors matching a named constant by their name, e.g. `gpu_color=green`. \
+    /// Off by default, since MangoHud itself only understands hex colors.
+    pub color_names: bool,
+    /// Write every bool, even the ones left at their default, e.g. `gpu_temp=0`,
+    /// to make it easy to see what's on and off.
+    pub explicit_bools: bool,
+    /// Wrap long `exec` commands onto `\` continuation lines. \
+    /// Off by default, since only this crate joins them back, MangoHud reads every line on its
+    /// own and would cut the command off.
+    pub wrap_exec: bool,
+    /// Write every option that is set, including the ones left at their default,
+    /// for a config listing everything MangoHud can be told.
+    pub all_options: bool,
+}
+
+impl MangoHudConfig {
+    /// Serializes the options that differ from [`MangoHudConfig::new`], one `key=value` per line
+    /// in the order of [`OPTIONS`](super::OPTIONS), followed by the unknown options kept in
+    /// [`MangoHudConfig::extra`]. The [`MangoHudConfig::header`] comments come first. \
+    /// Values are never wrapped, as MangoHud reads exactly one option per line.
+    ///
+    /// Fails on a value containing a line break, which MangoHud would read as the start of
+    /// another option, or ending in `\`, which would be read as a line continuation swallowing
+    /// the next option.
+    pub fn to_config_string(&self) -> Result<String> {
+        self.to_config_string_with(&SerializeOptions::default())
+    }
+
+    /// [`MangoHudConfig::to_config_string`] with the given options.
+    pub fn to_config_string_with(&self, options: &SerializeOptions) -> Result<String> {
+        let mut out = String::new();
+
+        for line in &self.header {
+            out.push_str(line);
+            out.push('\n');
+        }
+        for (key, value) in self.serialized_entries(options) {
+            if value.contains(['\n', '\r']) {
+                eyre::bail!("`{key}` contains a line break, which can't be written to a config");
+            }
+            if value.ends_with('\\') {
+                eyre::bail!("`{key}` ends in a `\\`, which can't be written to a config");
+            }
+
+            let value = if key == "exec" && options.wrap_exec {
+                wrap_continuation(&value, EXEC_WRAP_WIDTH)
+            } else {
+                value
+            };
+            out.push_str(&format!("{key}={value}\n"));
+        }
+
+        Ok(out)
+    }
+
+    /// Writes [`MangoHudConfig::to_config_string`] to `path`, which [`parse`](super::parse)
+    /// reads back into an equal config.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_with(path, &SerializeOptions::default())
+    }
+
+    /// [`MangoHudConfig::write`] with the given options. \
+    /// Fails without writing anything if a value can't be written to a config,
+    /// see [`MangoHudConfig::to_config_string`].
+    pub fn write_with<P: AsRef<Path>>(&self, path: P, options: &SerializeOptions) -> Result<()> {
+        let content = self.to_config_string_with(options)?;
+        fs_err::write(path.as_ref(), content)?;
+        Ok(())
+    }
+
+    /// Every `key=value` pair to write, known options first.
+    fn serialized_entries<'a>(
+        &'a self,
+        options: &'a SerializeOptions,
+    ) -> impl Iterator<Item = (&'a str, String)> + 'a {
+        self.changed_options(options)
+            .map(|(option, value)| (option.key, value))
+            .chain(
+                self.extra
+                    .iter()
+                    .map(|(key, extra)| (key.as_str(), extra.value.clone())),
+            )
+    }
+
+    /// The options that differ from [`MangoHudConfig::new`] as `(group, key, value)`,
+    /// in the order of [`OPTIONS`](super::OPTIONS), so options of the same group are next to each other.
+    pub fn to_entries(&self) -> Vec<(&'static str, &'static str, String)> {
+        self.changed_options(&SerializeOptions::default())
+            .map(|(option, value)| (option.group, option.key, value))
+            .collect()
+    }
+}
+
+/// Same as [`MangoHudConfig::to_config_string`]. \
+/// Formatting fails for a config that can't be written, so `to_string()` panics on it,
+/// use [`MangoHudConfig::to_config_string`] where values may contain line breaks.
+impl fmt::Display for MangoHudConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_config_string().map_err(|_| fmt::Error)?)
+    }
+}
+
+impl From<MangoHudConfig> for Vec<(&'static str, &'static str, String)> {
+    fn from(config: MangoHudConfig) -> Self {
+        config.to_entries()
+    }
+}
+
+/// Wraps `value` at whitespace using trailing `\` line continuations. \
+/// Continuation lines are indented, as leading whitespace is dropped when joining them back.
+fn wrap_continuation(value: &str, width: usize) -> String {
+    let mut lines = vec![String::new()];
+
+    for word in value.split(' ') {
+        let line = lines.last_mut().expect("there's always a line");
+        if !line.is_empty() && line.len() + word.len() > width {
+            line.push_str(" \\");
+            lines.push(format!("    {word}"));
+        } else {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{parse, parse_str, OPTIONS};
+
+    #[test]
+    fn write_round_trips() {
+        let mut config = MangoHudConfig::new();
+        for (key, value) in [
+            ("gpu_temp", "1"),
+            ("fps", "0"),
+            ("fps_sampling_period", "250"),
+            ("text_color", "FF8000"),
+            ("fps_limit", "30,60"),
+            ("blacklist", "steam,lutris"),
+        ] {
+            config.set_by_key(key, value).unwrap();
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("MangoHud.conf");
+        config.write(&path).unwrap();
+        assert_eq!(parse(&path).unwrap(), config);
+
+        let content = fs_err::read_to_string(&path).unwrap();
+        for line in [
+            "gpu_temp=1",
+            "fps=0",
+            "fps_sampling_period=250",
+            "text_color=ff8000",
+            "fps_limit=30,60",
+            "blacklist=steam,lutris",
+        ] {
+            assert!(content.lines().any(|l| l == line), "{line}:\n{content}");
+        }
+    }
+
+    #[test]
+    fn defaults_are_skipped_unless_asked_for() {
+        let config = MangoHudConfig::new();
+        assert_eq!(config.to_config_string().unwrap(), "");
+
+        let all = config
+            .to_config_string_with(&SerializeOptions {
+                all_options: true,
+                ..Default::default()
+            })
+            .unwrap();
+        // Unset options like `vsync` have nothing to write
+        let set = OPTIONS
+            .iter()
+            .filter(|option| config.get_by_key(option.key).is_some())
+            .count();
+        assert_eq!(all.lines().count(), set);
+        assert_eq!(parse_str(&all).unwrap(), config);
+    }
+
+    #[test]
+    fn long_values_stay_on_one_line() {
+        let mut config = MangoHudConfig::new();
+        config
+            .set_by_key("exec", &["echo long"; 20].join(" "))
+            .unwrap();
+        config
+            .set_by_key("custom_text", &"text ".repeat(30))
+            .unwrap();
+
+        let content = config.to_config_string().unwrap();
+        assert_eq!(content.lines().count(), 2, "{content}");
+    }
+
+    #[test]
+    fn line_break_in_custom_text_is_rejected() {
+        let mut config = MangoHudConfig::new();
+        config
+            .set_by_key("custom_text", "Hello\nfps_limit=10")
+            .unwrap();
+
+        let error = config.to_config_string().unwrap_err().to_string();
+        assert!(error.contains("`custom_text`"), "{error}");
+        assert!(fmt::write(&mut String::new(), format_args!("{config}")).is_err());
+    }
+
+    #[test]
+    fn trailing_backslash_is_rejected() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("exec", "echo \\").unwrap();
+        config.set_by_key("media_player", "1").unwrap();
+
+        // Written as is, the `exec` line would continue into `media_player=1`
+        assert!(config
+            .to_config_string_with(&SerializeOptions::default())
+            .is_err());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("MangoHud.conf");
+        assert!(config.write_with(&path, &Default::default()).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn wrapped_exec_reads_back() {
+        let mut config = MangoHudConfig::new();
+        let command = ["echo a\\b"; 20].join(" ");
+        config.set_by_key("exec", &command).unwrap();
+        config.set_by_key("media_player", "1").unwrap();
+
+        let content = config
+            .to_config_string_with(&SerializeOptions {
+                wrap_exec: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(content.lines().count() > 2);
+        let read = parse_str(&content).unwrap();
+        assert_eq!(read.get_by_key("exec").as_deref(), Some(command.as_str()));
+        assert_eq!(read.get_by_key("media_player").as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn color_names_only_when_asked_for() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("gpu_color", "00FF00").unwrap();
+        config.set_by_key("cpu_color", "123456").unwrap();
+
+        assert!(config
+            .to_config_string()
+            .unwrap()
+            .contains("gpu_color=00ff00\n"));
+
+        let named = config
+            .to_config_string_with(&SerializeOptions {
+                color_names: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(named.contains("gpu_color=green\n"), "{named}");
+        assert!(named.contains("cpu_color=123456\n"), "{named}");
+        assert_eq!(parse_str(&named).unwrap(), config);
+    }
+
+    #[test]
+    fn entries_follow_the_group_order() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("gpu_temp", "1").unwrap();
+        config.set_by_key("fps_limit", "60").unwrap();
+
+        let entries: Vec<(&str, &str, String)> = config.into();
+        assert_eq!(
+            entries,
+            [
+                ("Performance", "fps_limit", "60".to_owned()),
+                ("GPU Info", "gpu_temp", "1".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn options_are_written_in_upstream_order() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("gpu_stats", "0").unwrap();
+        config.set_by_key("fps_limit", "60").unwrap();
+
+        assert_eq!(
+            config.to_config_string().unwrap(),
+            "fps_limit=60\ngpu_stats=0\n"
+        );
+    }
+
+    #[test]
+    fn explicit_bools_writes_disabled_ones() {
+        let config = MangoHudConfig::new();
+        let options = SerializeOptions {
+            explicit_bools: true,
+            ..Default::default()
+        };
+        let content = config.to_config_string_with(&options).unwrap();
+        assert!(
+            content.lines().any(|line| line == "gpu_temp=0"),
+            "{content}"
+        );
+        assert!(
+            content.lines().any(|line| line == "gpu_stats=1"),
+            "{content}"
+        );
+        // Only bools are written, not every option
+        assert!(!content.lines().any(|line| line.starts_with("fps_limit=")));
+        assert_eq!(parse_str(&content).unwrap(), config);
+    }
+}
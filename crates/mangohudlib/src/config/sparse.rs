@@ -0,0 +1,115 @@
+//! Configs holding only the options a file actually sets, without the defaults filled in.
+
+use std::{collections::BTreeMap, path::Path};
+
+use eyre::{Result, WrapErr};
+
+use super::{read_entries, Entry, ExtraValue, MangoHudConfig, OPTIONS};
+
+/// The options set by a config file, in their normalized MangoHud representation,
+/// e.g. `1` for `gpu_stats=true`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SparseConfig {
+    values: BTreeMap<&'static str, String>,
+    /// See [`MangoHudConfig::extra`].
+    extra: BTreeMap<String, ExtraValue>,
+}
+
+impl SparseConfig {
+    /// The value of an option, `None` if the config doesn't set it.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key.trim()).map(String::as_str)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key.trim())
+    }
+
+    /// Every known option that's set, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> + '_ {
+        self.values
+            .iter()
+            .map(|(&key, value)| (key, value.as_str()))
+    }
+
+    /// Number of options that are set, known or not.
+    pub fn len(&self) -> usize {
+        self.values.len() + self.extra.len()
+    }
+
+    /// Whether the config sets no options at all, known or not.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.extra.is_empty()
+    }
+
+    /// Options not known to this crate, see [`MangoHudConfig::extra`].
+    pub fn extra(&self) -> &BTreeMap<String, ExtraValue> {
+        &self.extra
+    }
+
+    /// Applies the options set by this config on top of `config`.
+    pub fn apply_to(&self, config: &mut MangoHudConfig) -> Result<()> {
+        for (key, value) in self.iter() {
+            config.set_by_key(key, value)?;
+        }
+        for (key, value) in &self.extra {
+            config.apply_entry(key, &value.value, &mut Vec::new())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses only the options set by a MangoHud config file, see [`SparseConfig`].
+pub fn parse_sparse<P: AsRef<Path>>(file: P) -> Result<SparseConfig> {
+    let file = file.as_ref();
+    parse_sparse_str(&fs_err::read_to_string(file)?)
+        .wrap_err_with(|| format!("Failed to parse {}", file.display()))
+}
+
+/// Parses only the options set in the content of a MangoHud config file, see [`SparseConfig`].
+pub fn parse_sparse_str(content: &str) -> Result<SparseConfig> {
+    let mut sparse = SparseConfig::default();
+    // Values are normalized by round-tripping them through a full config
+    let mut scratch = MangoHudConfig::new();
+
+    for Entry { key, value, .. } in read_entries(content, &mut Vec::new())? {
+        let Some(option) = OPTIONS.iter().find(|option| option.key == key.trim()) else {
+            sparse
+                .extra
+                .insert(key.trim().into(), ExtraValue::new(&value));
+            continue;
+        };
+        scratch
+            .set_by_key(option.key, &value)
+            .wrap_err_with(|| format!("Failed to parse `{key}={value}`"))?;
+        let value = scratch
+            .get_by_key(option.key)
+            .unwrap_or_else(|| value.trim().into());
+        sparse.values.insert(option.key, value);
+    }
+
+    Ok(sparse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn len_counts_unknown_options() {
+        let sparse = parse_sparse_str("gpu_stats=true\nnew_option=1\n").unwrap();
+        assert_eq!(sparse.get("gpu_stats"), Some("1"));
+        assert_eq!(sparse.iter().count(), 1);
+        assert_eq!(sparse.extra().len(), 1);
+        assert_eq!(sparse.len(), 2);
+        assert!(!sparse.is_empty());
+
+        let sparse = parse_sparse_str("new_option=1\n").unwrap();
+        assert_eq!(sparse.len(), 1);
+        assert!(!sparse.is_empty());
+
+        let sparse = parse_sparse_str("").unwrap();
+        assert_eq!(sparse.len(), 0);
+        assert!(sparse.is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! Checks for values MangoHud would reject or silently ignore.
+
+use std::{fmt, ops::RangeInclusive};
+
+use super::{
+    constraint::{
+        AF_RANGE, ALPHA_RANGE, FCAT_OVERLAY_WIDTH_RANGE, PICMIP_RANGE, TABLE_COLUMNS_RANGE,
+        TEXT_OUTLINE_THICKNESS_RANGE,
+    },
+    MangoHudConfig,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub key: &'static str,
+    pub kind: ValidationErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// Value lies outside of the range MangoHud accepts.
+    OutOfRange { value: String, range: String },
+    /// Value is valid, but likely not what the user intended.
+    Warning(String),
+}
+
+impl ValidationError {
+    pub fn out_of_range<T: fmt::Display>(
+        key: &'static str,
+        value: T,
+        range: &RangeInclusive<T>,
+    ) -> Self {
+        Self {
+            key,
+            kind: ValidationErrorKind::OutOfRange {
+                value: value.to_string(),
+                range: format!("[{}, {}]", range.start(), range.end()),
+            },
+        }
+    }
+
+    pub fn is_warning(&self) -> bool {
+        matches!(self.kind, ValidationErrorKind::Warning(_))
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ValidationErrorKind::OutOfRange { value, range } => {
+                write!(f, "`{}` is out of range: {value} not in {range}", self.key)
+            }
+            ValidationErrorKind::Warning(msg) => write!(f, "`{}`: {msg}", self.key),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl MangoHudConfig {
+    /// Reports every out of range value and suspicious combination of options at once.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        // Only set through `set_by_key` if in range, but the fields can be set directly too
+        if let Some(picmip) = self.picmip {
+            check_range(&mut errors, "picmip", picmip.into(), PICMIP_RANGE);
+        }
+        if let Some(af) = self.af {
+            check_range(&mut errors, "af", af.into(), AF_RANGE);
+        }
+        check_range(&mut errors, "alpha", self.alpha, ALPHA_RANGE);
+        check_range(
+            &mut errors,
+            "background_alpha",
+            self.background_alpha,
+            ALPHA_RANGE,
+        );
+        check_range(
+            &mut errors,
+            "text_outline_thickness",
+            self.text_outline_thickness,
+            TEXT_OUTLINE_THICKNESS_RANGE,
+        );
+        check_range(
+            &mut errors,
+            "table_columns",
+            self.table_columns,
+            TABLE_COLUMNS_RANGE,
+        );
+        check_range(
+            &mut errors,
+            "fcat_overlay_width",
+            self.fcat_overlay_width,
+            FCAT_OVERLAY_WIDTH_RANGE,
+        );
+
+        errors.extend(self.lint().into_iter().map(|lint| ValidationError {
+            key: lint.key,
+            kind: ValidationErrorKind::Warning(lint.message),
+        }));
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn check_range<T>(
+    errors: &mut Vec<ValidationError>,
+    key: &'static str,
+    value: T,
+    range: RangeInclusive<T>,
+) where
+    T: PartialOrd + fmt::Display,
+{
+    if !range.contains(&value) {
+        errors.push(ValidationError::out_of_range(key, value, &range));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors_for(config: &MangoHudConfig, key: &str) -> Vec<ValidationError> {
+        config
+            .validate()
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|error| error.key == key)
+            .collect()
+    }
+
+    #[test]
+    fn text_outline_thickness_out_of_range() {
+        let mut config = MangoHudConfig::new();
+        config.text_outline_thickness = 20.0;
+
+        let errors = errors_for(&config, "text_outline_thickness");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].kind,
+            ValidationErrorKind::OutOfRange { .. }
+        ));
+    }
+
+    #[test]
+    fn text_outline_thickness_without_text_outline() {
+        let mut config = MangoHudConfig::new();
+        config.text_outline = false;
+        config.text_outline_thickness = 2.0;
+
+        let errors = errors_for(&config, "text_outline_thickness");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_warning());
+    }
+
+    #[test]
+    fn battery_icon_without_battery() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("battery_icon", "1").unwrap();
+        config.set_by_key("battery", "0").unwrap();
+
+        let errors = errors_for(&config, "battery_icon");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_warning());
+
+        config.set_by_key("battery", "1").unwrap();
+        assert!(errors_for(&config, "battery_icon").is_empty());
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let mut config = MangoHudConfig::new();
+        config.picmip = Some(-20);
+        config.af = Some(17);
+        config.alpha = 1.5;
+        config.background_alpha = -0.5;
+        config.gpu_mem_clock = true;
+        config.horizontal_stretch = false;
+
+        let errors = config.validate().unwrap_err();
+        let out_of_range: Vec<_> = errors
+            .iter()
+            .filter(|error| !error.is_warning())
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            out_of_range,
+            [
+                "`picmip` is out of range: -20 not in [-16, 16]",
+                "`af` is out of range: 17 not in [0, 16]",
+                "`alpha` is out of range: 1.5 not in [0, 1]",
+                "`background_alpha` is out of range: -0.5 not in [0, 1]",
+            ]
+        );
+
+        let warnings: Vec<_> = errors
+            .iter()
+            .filter(|error| error.is_warning())
+            .map(|error| error.key)
+            .collect();
+        assert_eq!(warnings, ["gpu_mem_clock", "horizontal_stretch"]);
+
+        assert!(MangoHudConfig::new().validate().is_ok());
+    }
+
+    #[test]
+    fn table_columns_out_of_range() {
+        let mut config = MangoHudConfig::new();
+        config.set_by_key("table_columns", "0").unwrap();
+
+        let errors = errors_for(&config, "table_columns");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "`table_columns` is out of range: 0 not in [1, 20]"
+        );
+
+        config.set_by_key("table_columns", "3").unwrap();
+        assert!(config.validate().is_ok());
+    }
+}
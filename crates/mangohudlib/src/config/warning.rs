@@ -0,0 +1,47 @@
+//! Problems found while parsing that don't prevent the config from being used.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    /// The key as written in the config, empty if the line couldn't be read as an entry.
+    pub key: String,
+    pub message: String,
+    /// Line of the config the warning is about, counted from 1. \
+    /// `None` for sources without lines, like `MANGOHUD_CONFIG`.
+    pub line: Option<usize>,
+    /// The entry was skipped rather than applied. Only reported by
+    /// [`parse_partial_str`](super::parse_partial_str), other parsers fail instead.
+    pub is_error: bool,
+}
+
+impl ParseWarning {
+    pub(super) fn new(key: &str, message: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            message: message.into(),
+            line: None,
+            is_error: false,
+        }
+    }
+
+    pub(super) fn error(key: &str, message: impl Into<String>, line: Option<usize>) -> Self {
+        Self {
+            line,
+            is_error: true,
+            ..Self::new(key, message)
+        }
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "Line {line}: ")?;
+        }
+        if !self.key.is_empty() {
+            write!(f, "`{}`: ", self.key)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
@@ -0,0 +1,48 @@
+//! XDG base directories, as MangoHud resolves them. \
+//! https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html
+
+use std::{env, path::PathBuf};
+
+/// Both `$XDG_CONFIG_HOME` and `~/.config`, in that order, for lookups that should find files in
+/// either, e.g. when `$XDG_CONFIG_HOME` is only set in some sessions.
+pub(crate) fn config_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for dir in [
+        xdg_dir("XDG_CONFIG_HOME", ".config"),
+        home_dir().map(|home| home.join(".config")),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// `$XDG_DATA_HOME`, falling back to `~/.local/share`.
+pub(crate) fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+/// `$XDG_RUNTIME_DIR`, which has no fallback per the spec.
+pub(crate) fn runtime_dir() -> Option<PathBuf> {
+    env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    // Relative paths are invalid per the spec and should be ignored
+    env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| home_dir().map(|home| home.join(fallback)))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
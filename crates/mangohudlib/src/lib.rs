@@ -1,2 +1,3 @@
-mod color;
+pub mod color;
 pub mod config;
+mod dirs;
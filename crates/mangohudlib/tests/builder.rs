@@ -0,0 +1,54 @@
+//! Building configs from outside the crate, where the fields aren't accessible.
+
+use mangohudlib::{
+    color::WHITE,
+    config::{HudPosition, MangoHudConfig},
+};
+
+#[test]
+fn builder_starts_from_the_defaults() {
+    let config = MangoHudConfig::builder()
+        .fps_limit(vec![60, 144])
+        .gpu_temp(true)
+        .position(HudPosition::BottomRight)
+        .text_color(WHITE)
+        .set("media_player", "1")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut expected = MangoHudConfig::new();
+    for (key, value) in [
+        ("fps_limit", "60,144"),
+        ("gpu_temp", "1"),
+        ("position", "bottom-right"),
+        ("text_color", "ffffff"),
+        ("media_player", "1"),
+    ] {
+        expected.set_by_key(key, value).unwrap();
+    }
+    assert_eq!(config, expected);
+    assert_eq!(
+        MangoHudConfig::builder().build().unwrap(),
+        MangoHudConfig::new()
+    );
+}
+
+#[test]
+fn builder_rejects_out_of_range_values() {
+    let errors = MangoHudConfig::builder()
+        .alpha(2.0)
+        .background_alpha(-1.0)
+        .build()
+        .unwrap_err();
+    let keys: Vec<_> = errors.iter().map(|error| error.key).collect();
+    assert_eq!(keys, ["alpha", "background_alpha"]);
+
+    // Warnings don't fail the build
+    assert!(MangoHudConfig::builder()
+        .vram(false)
+        .set("gpu_mem_clock", "1")
+        .unwrap()
+        .build()
+        .is_ok());
+}
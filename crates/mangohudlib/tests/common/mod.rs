@@ -0,0 +1,9 @@
+//! Helpers shared by the integration tests.
+
+use std::{fs, path::Path};
+
+/// Writes `content` to `path`, creating its parent directories.
+pub fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
@@ -0,0 +1,56 @@
+//! MangoHud's config file lookup order, see [`config::config_candidates`]. \
+//! Files are added one at a time, checking after each step which candidate wins, so the
+//! steps build on each other and are a single test.
+
+use std::env;
+
+use common::write;
+use mangohudlib::config::{self, MangoHudConfig};
+
+mod common;
+
+#[test]
+fn locate_config_order() {
+    let root = tempfile::tempdir().unwrap();
+    let xdg = root.path().join("xdg");
+    let home = root.path().join("home");
+    env::set_var("XDG_CONFIG_HOME", &xdg);
+    env::set_var("XDG_DATA_HOME", root.path().join("data"));
+    env::set_var("HOME", &home);
+    env::remove_var("MANGOHUD_CONFIGFILE");
+
+    assert_eq!(config::locate_config(None), None);
+    assert_eq!(MangoHudConfig::load(None).unwrap(), MangoHudConfig::new());
+
+    // `~/.config` is a fallback for `$XDG_CONFIG_HOME`
+    let home_global = home.join(".config/MangoHud/MangoHud.conf");
+    write(&home_global, "fps_limit=30\n");
+    assert_eq!(config::locate_config(None), Some(home_global));
+
+    let xdg_global = xdg.join("MangoHud/MangoHud.conf");
+    write(&xdg_global, "fps_limit=60\n");
+    assert_eq!(config::locate_config(None), Some(xdg_global.clone()));
+    assert_eq!(config::locate_config(Some("vkcube")), Some(xdg_global));
+
+    let per_app = home.join(".config/MangoHud/vkcube.conf");
+    write(&per_app, "fps_limit=90\n");
+    assert_eq!(config::locate_config(Some("vkcube")), Some(per_app));
+    let config = MangoHudConfig::load(Some("vkcube")).unwrap();
+    assert_eq!(config.get_by_key("fps_limit").as_deref(), Some("90"));
+
+    // The file given by `MANGOHUD_CONFIGFILE` is the only candidate
+    let file = root.path().join("custom.conf");
+    env::set_var("MANGOHUD_CONFIGFILE", &file);
+    assert_eq!(
+        config::config_candidates(Some("vkcube")),
+        vec![file.clone()]
+    );
+    assert_eq!(config::locate_config(Some("vkcube")), None);
+    write(&file, "fps_limit=120\n");
+    assert_eq!(config::locate_config(Some("vkcube")), Some(file));
+
+    assert_eq!(
+        config::default_output_folder(),
+        Some(root.path().join("data/MangoHud"))
+    );
+}
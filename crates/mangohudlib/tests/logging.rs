@@ -0,0 +1,27 @@
+//! Where [`MangoHudConfig::enable_logging`] writes logs without a folder given. \
+//! In its own file, as the default folder is resolved from `$XDG_DATA_HOME`.
+
+use std::{env, time::Duration};
+
+use mangohudlib::config::{self, MangoHudConfig};
+
+#[test]
+fn enable_logging_defaults_to_the_data_dir() {
+    let root = tempfile::tempdir().unwrap();
+    env::set_var("XDG_DATA_HOME", root.path());
+
+    let folder = root.path().join("MangoHud");
+    assert_eq!(config::default_output_folder(), Some(folder.clone()));
+
+    let mut config = MangoHudConfig::new();
+    config
+        .enable_logging(None, Duration::from_secs(60), Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(
+        config.get_by_key("output_folder"),
+        Some(folder.display().to_string())
+    );
+    assert_eq!(config.effective_output_folder(), Some(folder.clone()));
+    assert!(folder.is_dir());
+    assert!(config.lint().is_empty());
+}
@@ -0,0 +1,43 @@
+//! Reading config files with [`config::parse`].
+
+use std::fs;
+
+use mangohudlib::config;
+
+#[test]
+fn parse_reads_every_kind_of_option() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("MangoHud.conf");
+    fs::write(
+        &path,
+        "gpu_temp\nfps=0\nposition=bottom-right\nfps_sampling_period=250\nlog_duration=30\n\
+         log_interval=100\nfps_value=20,40\ngpu_load_color=ff0000,00ff00,0000ff\nnew_option=x\n",
+    )
+    .unwrap();
+
+    let config = config::parse(&path).unwrap();
+    for (key, value) in [
+        // A bare key enables the option
+        ("gpu_temp", "1"),
+        ("fps", "0"),
+        ("position", "bottom-right"),
+        ("fps_sampling_period", "250"),
+        ("fps_value", "20,40"),
+        ("gpu_load_color", "ff0000,00ff00,0000ff"),
+    ] {
+        assert_eq!(config.get_by_key(key).as_deref(), Some(value), "{key}");
+    }
+    assert_eq!(config.log_duration_secs(), 30);
+    assert_eq!(config.log_interval_ms(), 100);
+    assert_eq!(config.extra()["new_option"].value, "x");
+}
+
+#[test]
+fn parse_errors_name_the_key_and_value() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("MangoHud.conf");
+    fs::write(&path, "gpu_temp=1\nalpha=opaque\n").unwrap();
+
+    let error = format!("{:#}", config::parse(&path).unwrap_err());
+    assert!(error.contains("`alpha=opaque`"), "{error}");
+}
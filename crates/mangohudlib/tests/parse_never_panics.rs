@@ -0,0 +1,37 @@
+//! Inputs that are likely to trip up the parser, see `fuzz/fuzz_targets/parse.rs`.
+//! Each of them should parse or fail with an error, without panicking.
+
+use mangohudlib::config;
+
+const INPUTS: &[&str] = &[
+    "[",
+    "[]\n=",
+    "=\n==\n=1",
+    "exec=a \\",
+    "\\\n\\\n",
+    "fps_limit=,,+",
+    "fps_limit=99999999999999999999",
+    "picmip=-9223372036854775808",
+    "log_duration=18446744073709551615",
+    "alpha=1e40",
+    "gpu_color=é",
+    "gpu_color=#",
+    "font_glyph_ranges=-",
+    "font_glyph_ranges=0xFFFFFFFFF-0x0",
+    "[a]\nalpha=0.1\n[b]\nalpha=0.2",
+    "\u{feff}fps",
+];
+
+#[test]
+fn parse_never_panics() {
+    for input in INPUTS {
+        for config in [config::parse_str(input), config::parse_env(input)]
+            .into_iter()
+            .flatten()
+        {
+            let _ = config.to_config_string();
+            let _ = config.validate();
+            let _ = config.summary();
+        }
+    }
+}
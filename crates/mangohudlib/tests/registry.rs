@@ -0,0 +1,48 @@
+//! Parity of the [`OPTIONS`] registry with the hand-written accessors it replaced. \
+//! `fixtures/all_options.conf` sets every option, and the hand-written `get_by_key` wrote each
+//! value back exactly as it's written there.
+
+use std::path::Path;
+
+use mangohudlib::config::{self, MangoHudConfig, OPTIONS};
+
+fn all_options() -> Vec<(String, String)> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/all_options.conf");
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let (key, value) = line.split_once('=').unwrap();
+            (key.into(), value.into())
+        })
+        .collect()
+}
+
+#[test]
+fn sample_sets_every_option() {
+    let mut keys: Vec<_> = all_options().into_iter().map(|(key, _)| key).collect();
+    keys.sort();
+    let mut expected: Vec<_> = OPTIONS.iter().map(|option| option.key).collect();
+    expected.sort();
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn registry_matches_hand_written_accessors() {
+    let entries = all_options();
+    let mut config = MangoHudConfig::new();
+    for (key, value) in &entries {
+        config.set_by_key(key, value).unwrap();
+    }
+
+    for (key, value) in &entries {
+        assert_eq!(config.get_by_key(key).as_ref(), Some(value), "{key}");
+    }
+
+    let content: String = entries
+        .iter()
+        .map(|(key, value)| format!("{key}={value}\n"))
+        .collect();
+    assert_eq!(config::parse_str(&content).unwrap(), config);
+}
@@ -0,0 +1,72 @@
+//! Precedence of the config sources [`config::resolve`] combines. \
+//! Kept to a single test, as the cases depend on environment variables shared by the process.
+
+use std::env;
+
+use common::write;
+use mangohudlib::config::{self, MangoHudConfig};
+
+mod common;
+
+fn get(config: &MangoHudConfig, key: &str) -> String {
+    config.get_by_key(key).unwrap()
+}
+
+#[test]
+fn resolve_precedence() {
+    let root = tempfile::tempdir().unwrap();
+    let xdg = root.path().join("xdg/MangoHud");
+    let home = root.path().join("home");
+    env::set_var("XDG_CONFIG_HOME", root.path().join("xdg"));
+    env::set_var("HOME", &home);
+    env::remove_var("MANGOHUD_CONFIG");
+    env::remove_var("MANGOHUD_CONFIGFILE");
+
+    write(&xdg.join("MangoHud.conf"), "fps_limit=30\ngpu_temp=1\n");
+    write(&xdg.join("vkcube.conf"), "fps_limit=60\n");
+    write(
+        &home.join(".config/MangoHud/wine-game.conf"),
+        "fps_limit=75\n",
+    );
+    let default_gpu_temp = get(&MangoHudConfig::new(), "gpu_temp");
+
+    // Only the most specific file is read, the global one isn't layered below it
+    let config = config::resolve(Some("vkcube")).unwrap();
+    assert_eq!(get(&config, "fps_limit"), "60");
+    assert_eq!(get(&config, "gpu_temp"), default_gpu_temp);
+
+    let config = config::resolve(None).unwrap();
+    assert_eq!(get(&config, "fps_limit"), "30");
+    assert_eq!(get(&config, "gpu_temp"), "1");
+
+    // Wine configs are found, in `~/.config` as well
+    let config = config::resolve(Some("game")).unwrap();
+    assert_eq!(get(&config, "fps_limit"), "75");
+
+    // An empty `MANGOHUD_CONFIGFILE` is ignored
+    env::set_var("MANGOHUD_CONFIGFILE", "");
+    let config = config::resolve(Some("vkcube")).unwrap();
+    assert_eq!(get(&config, "fps_limit"), "60");
+
+    let file = root.path().join("custom.conf");
+    write(&file, "fps_limit=90\n");
+    env::set_var("MANGOHUD_CONFIGFILE", &file);
+    let config = config::resolve(Some("vkcube")).unwrap();
+    assert_eq!(get(&config, "fps_limit"), "90");
+    env::remove_var("MANGOHUD_CONFIGFILE");
+
+    // `MANGOHUD_CONFIG` replaces the file, unless it asks for it with `read_cfg`
+    env::set_var("MANGOHUD_CONFIG", "cpu_temp=1");
+    let config = config::resolve(None).unwrap();
+    assert_eq!(
+        get(&config, "fps_limit"),
+        get(&MangoHudConfig::new(), "fps_limit")
+    );
+    assert_eq!(get(&config, "cpu_temp"), "1");
+
+    env::set_var("MANGOHUD_CONFIG", "read_cfg,fps_limit=120");
+    let config = config::resolve(None).unwrap();
+    assert_eq!(get(&config, "fps_limit"), "120");
+    assert_eq!(get(&config, "gpu_temp"), "1");
+    assert!(config.extra().is_empty());
+}
@@ -0,0 +1,28 @@
+//! Parses every config in `tests/fixtures`, writes it back and parses the result again,
+//! which should give the same config. New fixtures are picked up automatically.
+
+use std::{fs, path::Path};
+
+use mangohudlib::config;
+
+#[test]
+fn fixtures_round_trip() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut count = 0;
+
+    for entry in fs::read_dir(&fixtures).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() != Some("conf".as_ref()) {
+            continue;
+        }
+
+        let parsed = config::parse(&path).unwrap();
+        let serialized = parsed.to_config_string().unwrap();
+        let reparsed = config::parse_str(&serialized)
+            .unwrap_or_else(|e| panic!("{}: {e:?}\n{serialized}", path.display()));
+        assert_eq!(parsed, reparsed, "{}:\n{serialized}", path.display());
+        count += 1;
+    }
+
+    assert!(count > 0, "no fixtures in {}", fixtures.display());
+}